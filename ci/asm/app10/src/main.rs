@@ -0,0 +1,43 @@
+#![no_main]
+#![no_std]
+
+use core::{
+    arch::asm,
+    ptr,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use cortex_m_semihosting::debug;
+use rt::{entry, set_interrupt_handler};
+
+// NVIC registers
+const ISER0: *mut u32 = 0xE000_E100 as *mut u32;
+const ISPR0: *mut u32 = 0xE000_E200 as *mut u32;
+
+static FIRED: AtomicBool = AtomicBool::new(false);
+
+entry!(main);
+
+fn main() -> ! {
+    unsafe {
+        // the Flash vector table has no entry for this interrupt
+        set_interrupt_handler(0, on_interrupt);
+
+        // enable and pend interrupt #0
+        ptr::write_volatile(ISER0, 1);
+        ptr::write_volatile(ISPR0, 1);
+        asm!("dsb", "isb");
+    }
+
+    if FIRED.load(Ordering::Relaxed) {
+        debug::exit(debug::EXIT_SUCCESS);
+    } else {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+
+    loop {}
+}
+
+extern "C" fn on_interrupt() {
+    FIRED.store(true, Ordering::Relaxed);
+}
@@ -0,0 +1,34 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m_semihosting::debug;
+use rt::{entry, exception, ExceptionFrame};
+
+// `static`s so that the compiler can't compute the product at compile time
+static A: f32 = 1.5;
+static B: f32 = 4.0;
+
+entry!(main);
+
+fn main() -> ! {
+    // without the `fpu` feature this `vmul.f32` instruction triggers a HardFault
+    let x = unsafe { ptr::read_volatile(&A) * ptr::read_volatile(&B) };
+
+    if x == 6.0 {
+        debug::exit(debug::EXIT_SUCCESS);
+    } else {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+
+    loop {}
+}
+
+exception!(HardFault, hard_fault);
+
+fn hard_fault(_ef: &ExceptionFrame) -> ! {
+    debug::exit(debug::EXIT_FAILURE);
+
+    loop {}
+}
@@ -0,0 +1,24 @@
+#![no_main]
+#![no_std]
+
+use core::arch::asm;
+
+use rt::{entry, heap_end, heap_start};
+
+entry!(main);
+
+fn main() -> ! {
+    extern "C" {
+        static _ebss: u8;
+    }
+
+    let ebss = unsafe { &_ebss as *const u8 as usize };
+    let (start, end) = (heap_start(), heap_end());
+
+    if start % 4 != 0 || start < ebss || start >= end {
+        // this makes QEMU crash
+        unsafe { asm!("BKPT") }
+    }
+
+    loop {}
+}
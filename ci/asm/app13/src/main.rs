@@ -0,0 +1,33 @@
+#![no_main]
+#![no_std]
+
+use core::{arch::asm, ptr};
+
+use rt::{entry, heap_end, heap_start, stack_used};
+
+entry!(main);
+
+fn main() -> ! {
+    let before = stack_used();
+
+    recurse(8);
+
+    let after = stack_used();
+
+    if before == 0 || after <= before || after >= heap_end() - heap_start() {
+        // this makes QEMU crash
+        unsafe { asm!("BKPT") }
+    }
+
+    loop {}
+}
+
+// each call uses at least 64 bytes of stack
+fn recurse(n: u32) {
+    let buffer = [n; 16];
+    unsafe { ptr::read_volatile(&buffer) };
+
+    if n != 0 {
+        recurse(n - 1);
+    }
+}
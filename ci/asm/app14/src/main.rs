@@ -0,0 +1,53 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m_semihosting::debug;
+use rt::{entry, exception, EXCEPTION_NUMBER};
+
+// System Handler Control and State Register
+const SHCSR: *mut u32 = 0xE000_ED24 as *mut u32;
+// System Handler Priority Registers
+const SHPR_BUS_FAULT: *mut u8 = 0xE000_ED19 as *mut u8;
+const SHPR_SYS_TICK: *mut u8 = 0xE000_ED23 as *mut u8;
+// SysTick registers
+const SYST_CSR: *mut u32 = 0xE000_E010 as *mut u32;
+const SYST_RVR: *mut u32 = 0xE000_E014 as *mut u32;
+
+// nothing is mapped at this address on the LM3S6965
+const UNMAPPED: *const u32 = 0x6000_0000 as *const u32;
+
+const BUS_FAULT: u16 = 5;
+
+entry!(main);
+
+fn main() -> ! {
+    unsafe {
+        // enable the BusFault exception; otherwise it escalates to HardFault
+        ptr::write_volatile(SHCSR, ptr::read_volatile(SHCSR) | (1 << 17));
+
+        // SysTick must be able to preempt the BusFault handler, which never returns
+        ptr::write_volatile(SHPR_BUS_FAULT, 0x80);
+        ptr::write_volatile(SHPR_SYS_TICK, 0x00);
+
+        ptr::write_volatile(SYST_RVR, 0x1_0000);
+        ptr::write_volatile(SYST_CSR, 0b111);
+
+        // this triggers a BusFault, which is handled by `DefaultExceptionHandler`
+        ptr::read_volatile(UNMAPPED);
+    }
+
+    // unreachable
+    debug::exit(debug::EXIT_FAILURE);
+
+    loop {}
+}
+
+exception!(SysTick, sys_tick);
+
+fn sys_tick() {
+    if unsafe { ptr::read_volatile(ptr::addr_of!(EXCEPTION_NUMBER)) } == BUS_FAULT {
+        debug::exit(debug::EXIT_SUCCESS);
+    }
+}
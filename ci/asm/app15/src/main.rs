@@ -0,0 +1,10 @@
+#![no_main]
+#![no_std]
+
+use rt::entry;
+
+entry!(main);
+
+fn main() -> ! {
+    loop {}
+}
@@ -0,0 +1,37 @@
+#![no_main]
+#![no_std]
+
+use core::{
+    ptr,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use cortex_m_semihosting::debug;
+use rt::{bind_interrupt, entry};
+
+// SysTick registers
+const SYST_CSR: *mut u32 = 0xE000_E010 as *mut u32;
+const SYST_RVR: *mut u32 = 0xE000_E014 as *mut u32;
+
+static TICKS: AtomicU32 = AtomicU32::new(0);
+
+entry!(main);
+
+fn main() -> ! {
+    unsafe {
+        ptr::write_volatile(SYST_RVR, 0x1_0000);
+        ptr::write_volatile(SYST_CSR, 0b111);
+    }
+
+    while TICKS.load(Ordering::Relaxed) < 3 {}
+
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
+
+bind_interrupt!(SysTick, sys_tick, resources = [TICKS]);
+
+fn sys_tick() {
+    TICKS.fetch_add(1, Ordering::Relaxed);
+}
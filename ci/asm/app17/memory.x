@@ -0,0 +1,6 @@
+/* A smaller device: 64 KiB of Flash and 16 KiB of RAM */
+MEMORY
+{
+  FLASH : ORIGIN = 0x00000000, LENGTH = 64K
+  RAM : ORIGIN = 0x20000000, LENGTH = 16K
+}
@@ -0,0 +1,26 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m_semihosting::debug;
+use rt::{entry, heap_start, stack_overflowed};
+
+entry!(main);
+
+fn main() -> ! {
+    if stack_overflowed() {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+
+    // simulate a stack overflow: the guard region sits right below the heap / stack space
+    unsafe { ptr::write_volatile((heap_start() - 4) as *mut u32, 0) }
+
+    if stack_overflowed() {
+        debug::exit(debug::EXIT_SUCCESS);
+    } else {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+
+    loop {}
+}
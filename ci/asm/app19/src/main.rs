@@ -0,0 +1,21 @@
+#![no_main]
+#![no_std]
+
+use cortex_m_semihosting::debug;
+use rt::entry;
+
+entry!(main);
+
+// `Reset` only calls `main` if the image matches the checksum
+fn main() -> ! {
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
+
+#[no_mangle]
+pub extern "C" fn ChecksumMismatch() -> ! {
+    debug::exit(debug::EXIT_FAILURE);
+
+    loop {}
+}
@@ -0,0 +1,43 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m_semihosting::debug;
+use rt::{entry, sections};
+
+// LM3S6965; see `memory.x`
+const FLASH_END: usize = 0x0004_0000;
+const RAM_START: usize = 0x2000_0000;
+const RAM_END: usize = 0x2001_0000;
+
+// these make `.bss` and `.data` non-empty
+static mut ZERO: u32 = 0;
+static mut ONE: u32 = 1;
+
+entry!(main);
+
+fn main() -> ! {
+    unsafe {
+        ptr::read_volatile(ptr::addr_of!(ZERO));
+        ptr::read_volatile(ptr::addr_of!(ONE));
+    }
+
+    let in_ram = |addr| addr >= RAM_START && addr <= RAM_END;
+
+    if sections::ebss() > sections::sbss()
+        && sections::edata() > sections::sdata()
+        && in_ram(sections::sbss())
+        && in_ram(sections::ebss())
+        && in_ram(sections::sdata())
+        && in_ram(sections::edata())
+        && in_ram(sections::stack_start())
+        && sections::sidata() < FLASH_END
+    {
+        debug::exit(debug::EXIT_SUCCESS);
+    } else {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+
+    loop {}
+}
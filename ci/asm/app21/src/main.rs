@@ -0,0 +1,27 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m_semihosting::debug;
+use rt::{entry, ramfunc};
+
+entry!(main);
+
+fn main() -> ! {
+    let x = unsafe { ptr::read_volatile(&20) };
+
+    if double(x) == 40 {
+        debug::exit(debug::EXIT_SUCCESS);
+    } else {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+
+    loop {}
+}
+
+// this runs from RAM
+#[ramfunc]
+fn double(x: u32) -> u32 {
+    x * 2
+}
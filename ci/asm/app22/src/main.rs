@@ -0,0 +1,12 @@
+#![no_main]
+#![no_std]
+
+use cortex_m_semihosting::debug;
+use rt::attr::entry;
+
+#[entry]
+fn main() -> ! {
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
@@ -0,0 +1,11 @@
+#![no_main]
+#![no_std]
+
+use rt::entry;
+
+entry!(main);
+
+// no `cortex-m-semihosting` needed to exit QEMU
+fn main() -> ! {
+    rt::exit(0)
+}
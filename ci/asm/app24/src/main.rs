@@ -0,0 +1,25 @@
+#![no_main]
+#![no_std]
+
+use core::arch::asm;
+
+use rt::{entry, exception, heap_end, ExceptionFrame};
+
+entry!(main);
+
+// `main` runs on the Main Stack Pointer (MSP), which starts at `heap_end`
+fn main() -> ! {
+    // this triggers a HardFault
+    unsafe { asm!("udf #0") }
+
+    rt::exit(1)
+}
+
+exception!(HardFault, hard_fault);
+
+// the exception frame was pushed onto the main stack
+fn hard_fault(ef: &ExceptionFrame) -> ! {
+    let addr = ef as *const ExceptionFrame as usize;
+
+    rt::exit(if addr < heap_end() && addr > heap_end() - 1024 { 0 } else { 1 })
+}
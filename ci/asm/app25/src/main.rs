@@ -0,0 +1,41 @@
+#![no_main]
+#![no_std]
+
+use core::{arch::asm, ptr};
+
+use rt::{entry, exception, ExceptionFrame};
+
+// the stack used by `main` after switching to the Process Stack Pointer (PSP); 8-byte aligned
+static mut PROCESS_STACK: [u64; 64] = [0; 64];
+
+entry!(main);
+
+fn main() -> ! {
+    unsafe {
+        let top = ptr::addr_of_mut!(PROCESS_STACK) as usize + 512;
+
+        // switch to the PSP (CONTROL.SPSEL = 1)
+        asm!(
+            "msr PSP, {top}",
+            "msr CONTROL, {spsel}",
+            "isb",
+            top = in(reg) top,
+            spsel = in(reg) 0b10,
+        );
+
+        // this triggers a HardFault
+        asm!("udf #0");
+    }
+
+    rt::exit(1)
+}
+
+exception!(HardFault, hard_fault);
+
+// the exception frame was pushed onto the process stack
+fn hard_fault(ef: &ExceptionFrame) -> ! {
+    let addr = ef as *const ExceptionFrame as usize;
+    let start = ptr::addr_of!(PROCESS_STACK) as usize;
+
+    rt::exit(if addr >= start && addr < start + 512 { 0 } else { 1 })
+}
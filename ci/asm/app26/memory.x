@@ -0,0 +1,6 @@
+/* No Flash: the whole image, vector table included, lives in the first half of the RAM */
+MEMORY
+{
+  FLASH : ORIGIN = 0x20000000, LENGTH = 32K
+  RAM : ORIGIN = 0x20008000, LENGTH = 32K
+}
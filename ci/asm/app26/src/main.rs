@@ -0,0 +1,30 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use rt::{entry, sections};
+
+// Vector Table Offset Register
+const VTOR: *const u32 = 0xE000_ED08 as *const u32;
+
+entry!(main);
+
+fn main() -> ! {
+    extern "C" {
+        static _svector_table: u8;
+    }
+
+    let vtor = unsafe { ptr::read_volatile(VTOR) } as usize;
+    let vector_table = unsafe { &_svector_table as *const u8 as usize };
+
+    // a local variable; it lives on the stack that `Reset` set up
+    let x = 0;
+    let sp = &x as *const i32 as usize;
+
+    if vtor == vector_table && sp < sections::stack_start() && sp > sections::stack_start() - 1024 {
+        rt::exit(0)
+    } else {
+        rt::exit(1)
+    }
+}
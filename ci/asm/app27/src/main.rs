@@ -0,0 +1,22 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use rt::entry;
+
+entry!(main);
+
+static BUFFER: [u8; 4] = [0, 1, 2, 3];
+
+// no panicking branches: this links with the `no-panic` feature
+fn main() -> ! {
+    // NOTE the volatile read hides the value of the index from the compiler
+    let index = unsafe { ptr::read_volatile(&4usize) };
+
+    // `get` reports an out of bounds index with `None` rather than by panicking
+    match BUFFER.get(index) {
+        None => rt::exit(0),
+        Some(_) => rt::exit(1),
+    }
+}
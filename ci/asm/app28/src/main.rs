@@ -0,0 +1,23 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use rt::entry;
+
+entry!(main);
+
+static BUFFER: [u8; 4] = [0, 1, 2, 3];
+
+// the bounds check of the indexing operation can panic: this must fail to link with the `no-panic`
+// feature
+fn main() -> ! {
+    // NOTE the volatile read hides the value of the index from the compiler
+    let index = unsafe { ptr::read_volatile(&4usize) };
+
+    if BUFFER[index] == 0 {
+        rt::exit(0)
+    } else {
+        rt::exit(1)
+    }
+}
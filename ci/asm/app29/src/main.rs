@@ -0,0 +1,16 @@
+#![no_main]
+#![no_std]
+
+use core::arch::asm;
+
+use rt::entry;
+
+entry!(main);
+
+// the `HardFault` handler provided by the `fault-report` feature reports the cause of the fault
+fn main() -> ! {
+    // permanently undefined instruction; this triggers a HardFault
+    unsafe { asm!("udf #0") }
+
+    rt::exit(0)
+}
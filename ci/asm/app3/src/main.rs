@@ -0,0 +1,41 @@
+#![no_main]
+#![no_std]
+
+use core::arch::asm;
+
+use rt::{backtrace, entry, ExceptionFrame};
+
+entry!(main);
+
+fn main() -> ! {
+    let (a, b) = (foo as fn() as usize, bar as fn() as usize);
+
+    // a synthetic exception frame followed by the stack of the interrupted code
+    let stack: [u32; 12] = [
+        // r0, r1, r2, r3, r12
+        0, 0, 0, 0, 0,
+        // lr: an EXC_RETURN value, not a return address
+        0xFFFF_FFF9,
+        // pc, xpsr
+        a as u32 & !1, 0x0100_0000,
+        // rest of the stack: a RAM address, a return address, an even value, a return address
+        0x2000_0000, a as u32, 0x1234, b as u32,
+    ];
+
+    let frame = unsafe { &*(stack.as_ptr() as *const ExceptionFrame) };
+    let mut out = [0; 2];
+
+    // check that only the return addresses were picked up
+    if backtrace(frame, &mut out) != 2 || out != [a, b] {
+        // this makes QEMU crash
+        unsafe { asm!("BKPT") }
+    }
+
+    loop {}
+}
+
+#[inline(never)]
+fn foo() {}
+
+#[inline(never)]
+fn bar() {}
@@ -0,0 +1,53 @@
+#![no_main]
+#![no_std]
+
+use core::{arch::asm, ptr};
+
+use rt::{entry, sections};
+
+// the stack used by `main` after switching to the Process Stack Pointer (PSP); 8-byte aligned
+static mut PROCESS_STACK: [u64; 64] = [0; 64];
+
+// set before jumping back to `Reset`; `Reset` doesn't touch static variables
+static mut RESTARTED: bool = false;
+
+entry!(main);
+
+fn main() -> ! {
+    unsafe {
+        if !ptr::read_volatile(ptr::addr_of!(RESTARTED)) {
+            ptr::write_volatile(ptr::addr_of_mut!(RESTARTED), true);
+
+            let top = ptr::addr_of_mut!(PROCESS_STACK) as usize + 512;
+
+            // switch to the PSP (CONTROL.SPSEL = 1), like a previous firmware may have done ..
+            asm!(
+                "msr PSP, {top}",
+                "msr CONTROL, {spsel}",
+                "isb",
+                top = in(reg) top,
+                spsel = in(reg) 0b10,
+            );
+
+            // .. and then jump to `Reset`, like a bootloader does
+            (rt::RESET_VECTOR)()
+        }
+
+        let control: u32;
+        asm!("mrs {}, CONTROL", out(reg) control);
+
+        // a local variable; it lives on the main stack
+        let x = 0;
+        let sp = &x as *const i32 as usize;
+
+        // SPSEL = 0 (MSP) and nPRIV = 0 (privileged)
+        if control & 0b11 == 0
+            && sp < sections::stack_start()
+            && sp > sections::stack_start() - 1024
+        {
+            rt::exit(0)
+        } else {
+            rt::exit(1)
+        }
+    }
+}
@@ -0,0 +1,6 @@
+/* The first 32K of Flash are taken by a bootloader */
+MEMORY
+{
+  FLASH : ORIGIN = 0x00008000, LENGTH = 224K
+  RAM : ORIGIN = 0x20000000, LENGTH = 64K
+}
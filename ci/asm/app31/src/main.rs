@@ -0,0 +1,11 @@
+#![no_main]
+#![no_std]
+
+use rt::entry;
+
+entry!(main);
+
+// `Reset` pointed VTOR to the vector table at the start of `FLASH`, i.e. at a 32K offset
+fn main() -> ! {
+    rt::exit(0)
+}
@@ -0,0 +1,42 @@
+#![no_main]
+#![no_std]
+
+use rt::{entry, reset_reason, IndependentWatchdog, ResetReason};
+
+// stand-in for the reset cause register (RESC) of the LM3S6965: the software reset flag is set
+static RESC: u32 = 1 << 4;
+
+entry!(main);
+
+fn main() -> ! {
+    // feed the watchdog well before its (~1 second at 12 MHz) timeout
+    let mut watchdog = IndependentWatchdog::start(12_000_000);
+    for _ in 0..3 {
+        watchdog.feed();
+    }
+
+    // `Reset` stored the reason reported by `__reset_reason`
+    if reset_reason() == ResetReason::Software {
+        rt::exit(0)
+    } else {
+        rt::exit(1)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn __reset_reason() -> ResetReason {
+    // power-on and brown-out resets also set the external reset flag so check those first
+    if RESC & (1 << 1) != 0 {
+        ResetReason::PowerOn
+    } else if RESC & (1 << 2) != 0 {
+        ResetReason::BrownOut
+    } else if RESC & (1 << 3) != 0 {
+        ResetReason::Watchdog
+    } else if RESC & (1 << 4) != 0 {
+        ResetReason::Software
+    } else if RESC & (1 << 0) != 0 {
+        ResetReason::External
+    } else {
+        ResetReason::Unknown
+    }
+}
@@ -0,0 +1,45 @@
+#![no_main]
+#![no_std]
+
+use core::{arch::asm, ptr};
+
+use rt::entry;
+
+// this survives a reset
+#[link_section = ".uninit.REBOOTED"]
+static mut REBOOTED: u32 = 0;
+
+const MAGIC: u32 = 0xDEAD_BEEF;
+
+entry!(main);
+
+fn main() -> ! {
+    unsafe {
+        if ptr::read_volatile(ptr::addr_of!(REBOOTED)) != MAGIC {
+            ptr::write_volatile(ptr::addr_of_mut!(REBOOTED), MAGIC);
+
+            // discard the garbage left in `.uninit` by the power-on reset, if any
+            rt::take_panic_message();
+
+            panic!("The answer is {}", 42);
+        }
+
+        ptr::write_volatile(ptr::addr_of_mut!(REBOOTED), 0);
+
+        // check that the location of the panic survived the reset and that it can only be taken once
+        if rt::take_panic_message() != Some("panicked at src/main.rs:24:13")
+            || rt::take_panic_message().is_some()
+        {
+            // this makes QEMU crash
+            asm!("BKPT")
+        }
+    }
+
+    loop {}
+}
+
+// simulate a watchdog reset
+#[no_mangle]
+pub extern "C" fn PanicReset() -> ! {
+    unsafe { rt::Reset() }
+}
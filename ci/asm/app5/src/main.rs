@@ -0,0 +1,43 @@
+#![no_main]
+#![no_std]
+
+use core::{
+    arch::asm,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use rt::{boot_mode, entry, BootMode};
+
+// stand-in for a boot configuration register
+static BOOT_CFG: AtomicU8 = AtomicU8::new(0);
+
+entry!(main);
+
+fn main() -> ! {
+    // check that each register value is reported as the right boot mode
+    for (value, mode) in [
+        (0, BootMode::Flash),
+        (1, BootMode::SystemMemory),
+        (3, BootMode::Ram),
+    ]
+    .iter()
+    {
+        BOOT_CFG.store(*value, Ordering::Relaxed);
+
+        if boot_mode() != *mode {
+            // this makes QEMU crash
+            unsafe { asm!("BKPT") }
+        }
+    }
+
+    loop {}
+}
+
+#[no_mangle]
+pub extern "C" fn __boot_mode() -> BootMode {
+    match BOOT_CFG.load(Ordering::Relaxed) & 0b11 {
+        0b01 => BootMode::SystemMemory,
+        0b11 => BootMode::Ram,
+        _ => BootMode::Flash,
+    }
+}
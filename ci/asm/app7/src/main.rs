@@ -0,0 +1,11 @@
+#![no_main]
+#![no_std]
+
+use rt::entry;
+
+entry!(main);
+
+// NOTE no exception handler is defined
+fn main() -> ! {
+    loop {}
+}
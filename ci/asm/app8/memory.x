@@ -0,0 +1 @@
+../app/memory.x
\ No newline at end of file
@@ -0,0 +1,37 @@
+#![no_main]
+#![no_std]
+
+use core::arch::asm;
+
+use cortex_m_semihosting::debug;
+use rt::{entry, exception, ExceptionFrame};
+
+entry!(main);
+
+fn main() -> ! {
+    // permanently undefined instruction; this triggers a HardFault
+    unsafe { asm!("udf #0") }
+
+    loop {}
+}
+
+exception!(HardFault, hard_fault);
+
+fn hard_fault(frame: &ExceptionFrame) -> ! {
+    extern "C" {
+        static _stext: u8;
+        static _etext: u8;
+    }
+
+    // the stacked PC should point to the `udf` instruction
+    let pc = frame.pc as usize;
+    let text = unsafe { &_stext as *const u8 as usize..&_etext as *const u8 as usize };
+
+    if text.contains(&pc) {
+        debug::exit(debug::EXIT_SUCCESS);
+    } else {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+
+    loop {}
+}
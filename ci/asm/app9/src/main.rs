@@ -0,0 +1,16 @@
+#![no_main]
+#![no_std]
+
+use device::Interrupt;
+use rt::{entry, interrupt};
+
+entry!(main);
+
+fn main() -> ! {
+    loop {}
+}
+
+// UART0 is left unhandled
+interrupt!(TIM2, tick);
+
+fn tick() {}
@@ -0,0 +1,116 @@
+//! Host side tool that stores the CRC32 of a program in its `.checksum` section
+//!
+//! `rt`'s `verify-checksum` feature recomputes the CRC32 of the `.text` and `.rodata` sections at
+//! boot and compares it against the value stored in `.checksum`. The linker can't compute the
+//! checksum so this has to be done after linking, on the ELF file.
+
+use std::convert::TryInto;
+
+// 32-bit ELF constants
+const ELFCLASS32: u8 = 1;
+const ELFDATA2LSB: u8 = 1;
+
+/// Computes the CRC32 of the image and stores it in the `.checksum` section of `elf`
+///
+/// Returns the CRC32 or `None` if `elf` is not a (well formed) 32-bit little endian ELF file or if
+/// it has no `.checksum` section
+pub fn patch(elf: &mut [u8]) -> Option<u32> {
+    let crc = image_crc(elf)?;
+    let checksum = section(elf, ".checksum")?;
+
+    elf.get_mut(checksum.offset..checksum.offset + 4)?
+        .copy_from_slice(&crc.to_le_bytes());
+
+    Some(crc)
+}
+
+/// Returns `true` if the CRC32 stored in the `.checksum` section of `elf` matches its image
+///
+/// Returns `None` if `elf` is not a (well formed) 32-bit little endian ELF file or if it has no
+/// `.checksum` section
+pub fn verify(elf: &[u8]) -> Option<bool> {
+    let checksum = section(elf, ".checksum")?;
+
+    Some(u32_at(elf, checksum.offset)? == image_crc(elf)?)
+}
+
+/// Computes the CRC32 of the image: the contents of the `.text` section followed by the contents of
+/// the `.rodata` section
+///
+/// This must match `rt::image_crc`
+pub fn image_crc(elf: &[u8]) -> Option<u32> {
+    let mut crc = !0;
+    for name in &[".text", ".rodata"] {
+        let section = section(elf, name)?;
+        crc = update(crc, elf.get(section.offset..section.offset + section.size)?);
+    }
+
+    Some(!crc)
+}
+
+// same bitwise implementation as the device
+fn update(mut crc: u32, bytes: &[u8]) -> u32 {
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    crc
+}
+
+struct Section {
+    name: u32,
+    offset: usize,
+    size: usize,
+}
+
+impl Section {
+    fn parse(elf: &[u8], offset: usize) -> Option<Self> {
+        Some(Section {
+            name: u32_at(elf, offset)?,
+            offset: u32_at(elf, offset + 16)? as usize,
+            size: u32_at(elf, offset + 20)? as usize,
+        })
+    }
+}
+
+// finds the section called `name`
+fn section(elf: &[u8], name: &str) -> Option<Section> {
+    if elf.get(..4)? != b"\x7fELF" || *elf.get(4)? != ELFCLASS32 || *elf.get(5)? != ELFDATA2LSB {
+        return None;
+    }
+
+    let shoff = u32_at(elf, 0x20)? as usize;
+    let shentsize = u16_at(elf, 0x2e)? as usize;
+    let shnum = u16_at(elf, 0x30)? as usize;
+    let shstrndx = u16_at(elf, 0x32)? as usize;
+
+    let shstrtab = Section::parse(elf, shoff + shstrndx * shentsize)?;
+
+    (0..shnum)
+        .filter_map(|i| Section::parse(elf, shoff + i * shentsize))
+        .find(|section| str_at(elf, &shstrtab, section.name) == Some(name))
+}
+
+fn u16_at(elf: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(elf.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+fn u32_at(elf: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(elf.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+// reads the NUL terminated string at `index` of the string table `strtab`
+fn str_at<'a>(elf: &'a [u8], strtab: &Section, index: u32) -> Option<&'a str> {
+    let start = strtab.offset + index as usize;
+    let bytes = elf.get(start..strtab.offset + strtab.size)?;
+    let end = bytes.iter().position(|b| *b == 0)?;
+
+    std::str::from_utf8(&bytes[..end]).ok()
+}
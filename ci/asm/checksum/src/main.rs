@@ -0,0 +1,41 @@
+//! Usage: `checksum [--verify] <elf>`
+//!
+//! Stores the CRC32 of the program in the `.checksum` section of the `elf` file. With `--verify` it
+//! instead checks the stored CRC32 and exits with an error if it doesn't match.
+
+use std::{env, fs, process};
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let (verify, path) = match &args[..] {
+        [path] => (false, path),
+        [flag, path] if flag == "--verify" => (true, path),
+        _ => {
+            eprintln!("usage: checksum [--verify] <elf>");
+            process::exit(1);
+        }
+    };
+
+    let mut elf = fs::read(path).expect("couldn't read the ELF file");
+
+    if verify {
+        match checksum::verify(&elf) {
+            Some(true) => println!("OK"),
+            Some(false) => {
+                eprintln!("checksum mismatch");
+                process::exit(1);
+            }
+            None => {
+                eprintln!("not a 32-bit ELF file with a `.checksum` section");
+                process::exit(1);
+            }
+        }
+    } else {
+        let crc = checksum::patch(&mut elf)
+            .expect("not a 32-bit ELF file with a `.checksum` section");
+
+        fs::write(path, &elf).expect("couldn't write the ELF file");
+
+        println!("{:#010x}", crc);
+    }
+}
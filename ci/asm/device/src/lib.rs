@@ -0,0 +1,5 @@
+//! A made up device with 32 interrupts
+
+#![no_std]
+
+rt::interrupts!(32; 5 => TIM2, 10 => UART0);
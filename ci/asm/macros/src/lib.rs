@@ -0,0 +1,154 @@
+//! Attributes re-exported by `rt`
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Span, TokenStream, TokenTree};
+
+/// Marks the entry point of the program; see `rt::attr::entry`
+#[proc_macro_attribute]
+pub fn entry(args: TokenStream, item: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        return error(Span::call_site(), "`#[entry]` takes no arguments");
+    }
+
+    let tokens = item.clone().into_iter().collect::<Vec<_>>();
+    if let Err((span, msg)) = check_entry(&tokens) {
+        return error(span, msg);
+    }
+
+    let mut output = "#[export_name = \"main\"]".parse::<TokenStream>().unwrap();
+    output.extend(item);
+    output.extend(
+        "
+        // a second entry point anywhere in the crate is rejected with \"the name
+        // `__entry_point_defined_multiple_times` is defined multiple times\"
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! __entry_point_defined_multiple_times {
+            () => {};
+        }"
+        .parse::<TokenStream>()
+        .unwrap(),
+    );
+    output
+}
+
+const NOT_A_FUNCTION: &str = "`#[entry]` must be applied to a function";
+const BAD_SIGNATURE: &str = "the entry point must have signature `fn() -> !`";
+
+// the entry point must be a `[pub] fn name() -> ! { .. }`, optionally with attributes
+fn check_entry(tokens: &[TokenTree]) -> Result<(), (Span, &'static str)> {
+    let mut tokens = tokens.iter().peekable();
+
+    // attributes, e.g. `#[inline(never)]`
+    while is_punct(tokens.peek().copied(), '#') {
+        tokens.next();
+        tokens.next();
+    }
+
+    // visibility, e.g. `pub` or `pub(crate)`
+    if is_ident(tokens.peek().copied(), "pub") {
+        tokens.next();
+        if let Some(TokenTree::Group(group)) = tokens.peek() {
+            if group.delimiter() == Delimiter::Parenthesis {
+                tokens.next();
+            }
+        }
+    }
+
+    match tokens.next() {
+        Some(tt) if is_ident(Some(tt), "fn") => {}
+        Some(tt) if is_ident(Some(tt), "async") => {
+            return Err((tt.span(), "the entry point can't be `async`"))
+        }
+        Some(tt) if is_ident(Some(tt), "const") => {
+            return Err((tt.span(), "the entry point can't be `const`"))
+        }
+        Some(tt) if is_ident(Some(tt), "unsafe") => {
+            return Err((tt.span(), "the entry point can't be `unsafe`"))
+        }
+        Some(tt) if is_ident(Some(tt), "extern") => {
+            return Err((tt.span(), "the entry point must use the Rust ABI"))
+        }
+        Some(tt) => return Err((tt.span(), NOT_A_FUNCTION)),
+        None => return Err((Span::call_site(), NOT_A_FUNCTION)),
+    }
+
+    // name
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(name)) => name.span(),
+        _ => return Err((Span::call_site(), NOT_A_FUNCTION)),
+    };
+
+    if is_punct(tokens.peek().copied(), '<') {
+        let span = tokens.next().unwrap().span();
+        return Err((span, "the entry point can't have type parameters"));
+    }
+
+    match tokens.next() {
+        Some(TokenTree::Group(args)) if args.delimiter() == Delimiter::Parenthesis => {
+            if !args.stream().is_empty() {
+                return Err((args.span(), "the entry point can't take arguments"));
+            }
+        }
+        _ => return Err((name, NOT_A_FUNCTION)),
+    }
+
+    // `-> !`
+    for punct in &['-', '>', '!'] {
+        match tokens.next() {
+            Some(tt) if is_punct(Some(tt), *punct) => {}
+            Some(tt) => return Err((tt.span(), BAD_SIGNATURE)),
+            None => return Err((name, BAD_SIGNATURE)),
+        }
+    }
+
+    match tokens.next() {
+        Some(TokenTree::Group(body)) if body.delimiter() == Delimiter::Brace => Ok(()),
+        Some(tt) if is_ident(Some(tt), "where") => {
+            Err((tt.span(), "the entry point can't have a `where` clause"))
+        }
+        _ => Err((name, BAD_SIGNATURE)),
+    }
+}
+
+/// Places the function in the `.ram_text` section, which `Reset` copies from Flash into RAM
+///
+/// The function must not be inlined into its callers; otherwise its code would end up in Flash.
+#[proc_macro_attribute]
+pub fn ramfunc(args: TokenStream, item: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        return error(Span::call_site(), "`#[ramfunc]` takes no arguments");
+    }
+
+    format!("#[link_section = \".ram_text\"] #[inline(never)] {}", item)
+        .parse()
+        .unwrap()
+}
+
+fn is_ident(tt: Option<&TokenTree>, ident: &str) -> bool {
+    match tt {
+        Some(TokenTree::Ident(i)) => i.to_string() == ident,
+        _ => false,
+    }
+}
+
+fn is_punct(tt: Option<&TokenTree>, punct: char) -> bool {
+    match tt {
+        Some(TokenTree::Punct(p)) => p.as_char() == punct,
+        _ => false,
+    }
+}
+
+// `compile_error!($msg)` pointing at `span`
+fn error(span: Span, msg: &str) -> TokenStream {
+    format!("compile_error!({:?});", msg)
+        .parse::<TokenStream>()
+        .unwrap()
+        .into_iter()
+        .map(|mut tt| {
+            tt.set_span(span);
+            tt
+        })
+        .collect()
+}
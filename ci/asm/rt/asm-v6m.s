@@ -0,0 +1,18 @@
+  .syntax unified
+  .section .text.HardFaultTrampoline
+  .global HardFaultTrampoline
+  .thumb_func
+HardFaultTrampoline:
+  /* ARMv6-M has no `it` instruction and `tst` only takes low registers */
+  mov r0, lr
+  movs r1, #4
+  tst r0, r1
+  bne 1f
+  mrs r0, MSP
+  b 2f
+1:
+  mrs r0, PSP
+2:
+  /* `b` can only reach +-2 KB on ARMv6-M */
+  ldr r1, =HardFault
+  bx r1
@@ -1,6 +1,10 @@
+  .syntax unified
   .section .text.HardFaultTrampoline
   .global HardFaultTrampoline
   .thumb_func
 HardFaultTrampoline:
   mrs r0, MSP
+  tst lr, #4
+  it ne
+  mrsne r0, PSP
   b HardFault
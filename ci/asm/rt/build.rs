@@ -12,6 +12,15 @@ fn main() -> Result<(), Box<dyn Error>> {
     // put `link.x` in the build directory
     File::create(out_dir.join("link.x"))?.write_all(include_bytes!("link.x"))?;
 
+    // ARMv6-M (Cortex-M0) doesn't support the `it` instruction used in `asm.s`. This version of the
+    // trampoline uses branches to pick the stack pointer (MSP or PSP) that holds the exception frame
+    if env::var("TARGET")?.starts_with("thumbv6m-") {
+        Build::new().file("asm-v6m.s").compile("asm");
+        println!("cargo:rerun-if-changed=asm-v6m.s");
+
+        return Ok(());
+    }
+
     // assemble the `asm.s` file
     Build::new().file("asm.s").compile("asm"); // <- NEW!
 
@@ -1,40 +1,66 @@
-/* Memory layout of the LM3S6965 microcontroller */
-/* 1K = 1 KiBi = 1024 bytes */
-MEMORY
-{
-  FLASH : ORIGIN = 0x00000000, LENGTH = 256K
-  RAM : ORIGIN = 0x20000000, LENGTH = 64K
-}
+/* The memory layout of the target device, i.e. the `FLASH` and `RAM` regions, is provided by the
+   application in a `memory.x` file placed in the root of its crate; e.g. for the LM3S6965:
+
+   MEMORY
+   {
+     FLASH : ORIGIN = 0x00000000, LENGTH = 256K
+     RAM : ORIGIN = 0x20000000, LENGTH = 64K
+   }
+
+   An image that lives behind a bootloader starts where the bootloader ends: move the `ORIGIN` of
+   `FLASH` to that offset, e.g. `0x00008000`, and enable the `set-vtor` feature so that `Reset`
+   points VTOR to the relocated vector table.
+
+   If the linker reports that it can't find `memory.x`, that file is missing */
+INCLUDE memory.x
 
 /* The entry point is the reset handler */
 ENTRY(Reset);
 
 EXTERN(RESET_VECTOR);
 EXTERN(EXCEPTIONS);
+EXTERN(__INTERRUPTS);
+
+/* Initial Stack Pointer value: the stack grows downwards from the end of RAM unless the application
+   overrides it, e.g. with `-C link-arg=--defsym=_stack_start=0x2000f000` */
+PROVIDE(_stack_start = ORIGIN(RAM) + LENGTH(RAM));
 
 SECTIONS
 {
   .vector_table ORIGIN(FLASH) :
   {
     /* First entry: initial Stack Pointer value */
-    LONG(ORIGIN(RAM) + LENGTH(RAM));
+    LONG(_stack_start);
 
     /* Second entry: reset vector */
     KEEP(*(.vector_table.reset_vector));
 
     /* The next 14 entries are exception vectors */
     KEEP(*(.vector_table.exceptions)); /* <- NEW */
+
+    /* Device specific interrupt vectors, if any; see `interrupts!` */
+    KEEP(*(.vector_table.interrupts));
   } > FLASH
 
   .text :
   {
+    _stext = .;
     *(.text .text.*);
+    _etext = .;
   } > FLASH
 
   /* CHANGED! */
   .rodata :
   {
+    _srodata = .;
     *(.rodata .rodata.*);
+    _erodata = .;
+  } > FLASH
+
+  /* CRC32 of `.text` and `.rodata`; see `image_crc` */
+  .checksum : ALIGN(4)
+  {
+    KEEP(*(.checksum));
   } > FLASH
 
   .bss :
@@ -44,7 +70,7 @@ SECTIONS
     _ebss = .;
   } > RAM
 
-  .data : AT(ADDR(.rodata) + SIZEOF(.rodata))
+  .data : AT(ADDR(.checksum) + SIZEOF(.checksum))
   {
     _sdata = .;
     *(.data .data.*);
@@ -53,12 +79,91 @@ SECTIONS
 
   _sidata = LOADADDR(.data);
 
+  /* Functions that run from RAM; see `#[ramfunc]`. Like `.data`, they are stored in Flash, right
+     after the initial values of `.data`, and copied into RAM by `Reset` */
+  .ram_text : AT(ALIGN(LOADADDR(.data) + SIZEOF(.data), 4)) ALIGN(4)
+  {
+    _sram_text = .;
+    *(.ram_text .ram_text.*);
+    . = ALIGN(4);
+    _eram_text = .;
+  } > RAM
+
+  _siram_text = LOADADDR(.ram_text);
+
+  /* Not initialized by `Reset`: the contents of this section survive a reset */
+  .uninit (NOLOAD) : ALIGN(4)
+  {
+    *(.uninit .uninit.*);
+  } > RAM
+
+  /* RAM copy of the vector table; only used by the `vtor-ram` feature */
+  .ram_vectors (NOLOAD) :
+  {
+    *(.ram_vectors);
+  } > RAM
+
+  /* Guard region painted by the `stack-guard` feature; the stack overflows into it before
+     reaching the static variables. The application sets its size, e.g. with
+     `-C link-arg=--defsym=_stack_guard_size=32` */
+  .stack_guard (NOLOAD) : ALIGN(4)
+  {
+    _sstack_guard = .;
+    . += _stack_guard_size;
+    _estack_guard = .;
+  } > RAM
+
+  /* The heap spans from here to the end of RAM; see `heap_start` */
+  .heap (NOLOAD) : ALIGN(4)
+  {
+    _sheap = .;
+  } > RAM
+
   /DISCARD/ :
   {
     *(.ARM.exidx .ARM.exidx.*);
   }
 }
 
+/* The hardware expects the vector table at the start of Flash: initial SP value, reset vector and
+   then the exception vectors, all of them 4-byte words */
+ASSERT(ADDR(.vector_table) == ORIGIN(FLASH), "the vector table must be at the start of FLASH");
+ASSERT(RESET_VECTOR == ORIGIN(FLASH) + 4, "RESET_VECTOR must be the 2nd vector table entry");
+ASSERT(EXCEPTIONS == ORIGIN(FLASH) + 8, "EXCEPTIONS must come right after RESET_VECTOR");
+
+/* The program must fit in Flash: the vector table, the code, the read-only data, the initial
+   values of `.data` and the functions that run from RAM are all stored there */
+ASSERT(SIZEOF(.vector_table) + SIZEOF(.text) + SIZEOF(.rodata) + SIZEOF(.checksum) + SIZEOF(.data)
+       + SIZEOF(.ram_text) <= LENGTH(FLASH), "
+ERROR(rt): the program doesn't fit in FLASH; reduce its size or check the FLASH region in memory.x");
+
+/* No stack guard region by default */
+PROVIDE(_stack_guard_size = 0);
+ASSERT(_stack_guard_size % 4 == 0, "
+ERROR(rt): `_stack_guard_size` must be a multiple of 4");
+
+/* AAPCS: the stack pointer must be 8-byte aligned at public interfaces, e.g. on entry to `main` */
+ASSERT(_stack_start % 8 == 0, "
+ERROR(rt): `_stack_start` must be 8-byte aligned");
+
+/* Minimum amount of RAM that must be left for the stack after the static variables; the
+   application can override this, e.g. with `-C link-arg=--defsym=_stack_size=0x1000` */
+PROVIDE(_stack_size = 0x400);
+
+/* The static variables must fit in RAM and leave at least `_stack_size` bytes for the stack */
+ASSERT(_sheap + _stack_size <= _stack_start, "
+ERROR(rt): not enough RAM left for the stack; reduce the size of the static variables or
+`_stack_size`, or check the RAM region in memory.x");
+
+/* Number of device specific interrupts; set by `interrupts!` */
+PROVIDE(NUM_INTERRUPTS = 0);
+
+/* No padding: the code starts right after the last interrupt vector */
+ASSERT(SIZEOF(.vector_table) == (16 + NUM_INTERRUPTS) * 4, "unexpected size of the vector table");
+ASSERT(_stext == ADDR(.vector_table) + SIZEOF(.vector_table), ".text must follow the vector table");
+
+/* Exceptions for which the application doesn't define a handler (a function with the exception's
+   name) are handled by `DefaultExceptionHandler` */
 PROVIDE(NMI = DefaultExceptionHandler);
 PROVIDE(HardFault = DefaultExceptionHandler);
 PROVIDE(MemManage = DefaultExceptionHandler);
@@ -67,3 +172,12 @@ PROVIDE(UsageFault = DefaultExceptionHandler);
 PROVIDE(SVCall = DefaultExceptionHandler);
 PROVIDE(PendSV = DefaultExceptionHandler);
 PROVIDE(SysTick = DefaultExceptionHandler);
+
+PROVIDE(PanicReset = DefaultPanicReset);
+PROVIDE(ChecksumMismatch = DefaultChecksumMismatch);
+PROVIDE(__boot_mode = DefaultBootMode);
+PROVIDE(__reset_reason = DefaultResetReason);
+
+/* Boundaries of the vector table in Flash; the `vtor-ram` feature copies it into RAM */
+_svector_table = ADDR(.vector_table);
+_evector_table = ADDR(.vector_table) + SIZEOF(.vector_table);
@@ -0,0 +1,40 @@
+/// Asserts that `$cond` is `true`
+///
+/// Unlike `core::assert!` this doesn't use the formatting machinery. On failure it logs, at the
+/// ERROR level, the address of an interned string that contains the location of the assertion,
+/// flushes the global logger and then halts.
+#[macro_export]
+macro_rules! assert {
+    ($cond:expr $(,)?) => {
+        if !$cond {
+            $crate::__assert_failed!();
+        }
+    };
+}
+
+/// Asserts that `$left` and `$right` are equal; see `rt::assert!`
+#[macro_export]
+macro_rules! assert_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !(*left == *right) {
+                    $crate::__assert_failed!();
+                }
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert_failed {
+    () => {{
+        // NOTE the condition is not part of the interned string because it may contain characters,
+        // like `"`, that can't appear in a symbol name
+        $crate::__log::error!(concat!("assertion failed at ", file!(), ":", line!()));
+        $crate::__log::flush!();
+
+        loop {}
+    }};
+}
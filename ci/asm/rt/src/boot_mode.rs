@@ -0,0 +1,37 @@
+/// Memory the device booted from
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(u8)]
+pub enum BootMode {
+    /// Main Flash memory
+    Flash,
+    /// System memory, where the vendor's bootloader usually lives
+    SystemMemory,
+    /// Embedded RAM
+    Ram,
+}
+
+/// Returns the memory the device booted from
+///
+/// How to tell the boot source is chip-specific (e.g. a boot configuration register or the state of
+/// the BOOT pins) so the default implementation is a stub that always returns `BootMode::Flash`.
+/// Override it by defining this function:
+///
+/// ``` ignore
+/// #[no_mangle]
+/// pub extern "C" fn __boot_mode() -> BootMode {
+///     // .. read the boot configuration register ..
+/// }
+/// ```
+pub fn boot_mode() -> BootMode {
+    extern "C" {
+        fn __boot_mode() -> BootMode;
+    }
+
+    unsafe { __boot_mode() }
+}
+
+// The default `__boot_mode`; see `link.x`
+#[no_mangle]
+pub extern "C" fn DefaultBootMode() -> BootMode {
+    BootMode::Flash
+}
@@ -0,0 +1,67 @@
+use core::{ptr, slice};
+
+/// CRC32 of the image, `.text` and `.rodata`, written by the host `checksum` tool after linking
+///
+/// The linker script places this right after `.rodata` so it's not part of its own checksum.
+#[link_section = ".checksum"]
+#[no_mangle]
+static __CHECKSUM: u32 = 0xFFFF_FFFF;
+
+/// Computes the CRC32 of the image: the contents of the `.text` section followed by the contents of
+/// the `.rodata` section
+///
+/// `Reset` compares this against the value that the host `checksum` tool stored in the `.checksum`
+/// section. The CRC is the one used by Ethernet and zlib (reflected, polynomial `0x04C11DB7`).
+pub fn image_crc() -> u32 {
+    extern "C" {
+        static _stext: u8;
+        static _etext: u8;
+        static _srodata: u8;
+        static _erodata: u8;
+    }
+
+    unsafe {
+        let crc = update(!0, region(&_stext, &_etext));
+        !update(crc, region(&_srodata, &_erodata))
+    }
+}
+
+/// Checks the image against the stored CRC32 and calls `ChecksumMismatch` if they don't match
+pub(crate) fn verify() {
+    let expected = unsafe { ptr::read_volatile(&__CHECKSUM) };
+
+    if image_crc() != expected {
+        extern "C" {
+            fn ChecksumMismatch() -> !;
+        }
+
+        unsafe { ChecksumMismatch() }
+    }
+}
+
+// Halts the program; see `link.x`
+#[no_mangle]
+pub extern "C" fn DefaultChecksumMismatch() -> ! {
+    loop {}
+}
+
+unsafe fn region(start: &u8, end: &u8) -> &'static [u8] {
+    let start = start as *const u8;
+    slice::from_raw_parts(start, end as *const u8 as usize - start as usize)
+}
+
+// bitwise CRC32; slower than a table driven implementation but doesn't take up Flash
+fn update(mut crc: u32, bytes: &[u8]) -> u32 {
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    crc
+}
@@ -0,0 +1,20 @@
+use core::{arch::asm, ptr};
+
+/// Number of the last exception handled by `DefaultExceptionHandler`
+///
+/// This is meant to be inspected with a debugger (`print EXCEPTION_NUMBER`) to find out which
+/// exception the program is stuck in: 2 is NMI, 3 is HardFault, 4 is MemManage, 5 is BusFault, 6 is
+/// UsageFault, etc. and `16 + n` is the `n`-th device specific interrupt. 0 means that no exception
+/// has reached `DefaultExceptionHandler`.
+#[no_mangle]
+pub static mut EXCEPTION_NUMBER: u16 = 0;
+
+// copies the number of the active exception from the IPSR register into `EXCEPTION_NUMBER`
+pub(crate) fn capture() {
+    let ipsr: u32;
+    unsafe {
+        asm!("mrs {}, IPSR", out(reg) ipsr, options(nomem, nostack, preserves_flags));
+
+        ptr::write_volatile(ptr::addr_of_mut!(EXCEPTION_NUMBER), ipsr as u16 & 0x1FF);
+    }
+}
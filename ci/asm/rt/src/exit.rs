@@ -0,0 +1,49 @@
+use core::arch::asm;
+
+// semihosting operation number of SYS_EXIT (`angel_SWIreason_ReportException`)
+#[cfg(feature = "semihosting")]
+const SYS_EXIT: u32 = 0x18;
+
+// reasons reported to the debugger / QEMU
+#[cfg(feature = "semihosting")]
+const ADP_STOPPED_APPLICATION_EXIT: u32 = 0x2_0026;
+#[cfg(feature = "semihosting")]
+const ADP_STOPPED_RUN_TIME_ERROR_UNKNOWN: u32 = 0x2_0023;
+
+/// Terminates the program with the given exit `code`; `0` indicates success
+///
+/// Without the `semihosting` feature this puts `code` in register `r0` and executes a `bkpt #0`
+/// instruction; a test harness attached through a debugger can read the exit code from `r0` when the
+/// processor halts. NOTE without a debugger attached the breakpoint escalates to a HardFault.
+///
+/// With the `semihosting` feature this performs the semihosting `SYS_EXIT` operation instead; QEMU
+/// (`-semihosting-config enable=on`) exits with status 0 if `code` is `0` and with status 1
+/// otherwise. NOTE 32-bit semihosting can only report success or failure, not the code itself.
+pub fn exit(code: u8) -> ! {
+    #[cfg(feature = "semihosting")]
+    unsafe {
+        let reason = if code == 0 {
+            ADP_STOPPED_APPLICATION_EXIT
+        } else {
+            ADP_STOPPED_RUN_TIME_ERROR_UNKNOWN
+        };
+
+        asm!(
+            "bkpt #0xab",
+            in("r0") SYS_EXIT,
+            in("r1") reason,
+            options(nomem, nostack, preserves_flags),
+        );
+    }
+
+    #[cfg(not(feature = "semihosting"))]
+    unsafe {
+        asm!(
+            "bkpt #0",
+            in("r0") u32::from(code),
+            options(nomem, nostack, preserves_flags),
+        );
+    }
+
+    loop {}
+}
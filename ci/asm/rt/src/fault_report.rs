@@ -0,0 +1,132 @@
+use core::{
+    arch::asm,
+    fmt::{self, Write},
+    ptr,
+};
+
+use crate::ExceptionFrame;
+
+// Configurable Fault Status Register and HardFault Status Register
+const CFSR: *const u32 = 0xE000_ED28 as *const u32;
+const HFSR: *const u32 = 0xE000_ED2C as *const u32;
+
+// semihosting operation number of SYS_WRITE0: writes a null-terminated string to the debug console
+const SYS_WRITE0: u32 = 0x04;
+
+// fault status bits, from most to least specific, and the reason they report
+const REASONS: &[(u32, &str)] = &[
+    // UsageFault Status Register (CFSR[31:16])
+    (1 << 16, "undefined instruction"),
+    (1 << 17, "invalid state (e.g. Thumb bit cleared)"),
+    (1 << 18, "invalid EXC_RETURN value loaded into the PC"),
+    (1 << 19, "coprocessor access (is the FPU enabled?)"),
+    (1 << 24, "unaligned memory access"),
+    (1 << 25, "division by zero"),
+    // BusFault Status Register (CFSR[15:8])
+    (1 << 8, "bus error on instruction fetch"),
+    (1 << 9, "precise bus error on data access"),
+    (1 << 10, "imprecise bus error on data access"),
+    (1 << 11, "bus error while unstacking"),
+    (1 << 12, "bus error while stacking"),
+    // MemManage Status Register (CFSR[7:0])
+    (1 << 0, "instruction access violation"),
+    (1 << 1, "data access violation"),
+    (1 << 3, "memory access violation while unstacking"),
+    (1 << 4, "memory access violation while stacking"),
+];
+
+// HFSR.VECTTBL
+const VECTTBL: u32 = 1 << 1;
+
+/// What the processor was doing when the `HardFault` occurred
+///
+/// With the `fault-report` feature `rt` provides a `HardFault` handler that prints this over
+/// semihosting and then exits with a non-zero code. You can also build one from your own `HardFault`
+/// handler, see `FaultInfo::read`.
+///
+/// NOTE ARMv7-M only; ARMv6-M devices don't have the fault status registers
+#[derive(Clone, Copy, Debug)]
+pub struct FaultInfo {
+    /// Registers stacked on exception entry
+    pub frame: ExceptionFrame,
+    /// Value of the Configurable Fault Status Register (`SCB.CFSR`)
+    pub cfsr: u32,
+    /// Value of the HardFault Status Register (`SCB.HFSR`)
+    pub hfsr: u32,
+}
+
+impl FaultInfo {
+    /// Reads the fault status registers
+    pub fn read(frame: &ExceptionFrame) -> Self {
+        unsafe {
+            FaultInfo {
+                frame: *frame,
+                cfsr: ptr::read_volatile(CFSR),
+                hfsr: ptr::read_volatile(HFSR),
+            }
+        }
+    }
+
+    /// Human readable cause of the fault
+    ///
+    /// If several fault status bits are set this reports the most specific one
+    pub fn reason(&self) -> &'static str {
+        for (mask, reason) in REASONS {
+            if self.cfsr & mask != 0 {
+                return reason;
+            }
+        }
+
+        if self.hfsr & VECTTBL != 0 {
+            "bus error while reading the vector table"
+        } else {
+            "unknown"
+        }
+    }
+}
+
+impl fmt::Display for FaultInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "HardFault: {} at PC = {:#010x} (CFSR = {:#010x}, HFSR = {:#010x})",
+            self.reason(),
+            self.frame.pc,
+            self.cfsr,
+            self.hfsr
+        )
+    }
+}
+
+// writes to the debug console using the semihosting SYS_WRITE0 operation
+struct Console;
+
+impl Write for Console {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        // the string must be null-terminated so it's sent in chunks through a buffer
+        let mut buffer = [0; 64];
+
+        for chunk in s.as_bytes().chunks(buffer.len() - 1) {
+            buffer[..chunk.len()].copy_from_slice(chunk);
+            buffer[chunk.len()] = 0;
+
+            unsafe {
+                asm!(
+                    "bkpt #0xab",
+                    inout("r0") SYS_WRITE0 => _,
+                    in("r1") buffer.as_ptr(),
+                    options(nostack, preserves_flags),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[export_name = "HardFault"]
+extern "C" fn hard_fault(frame: &ExceptionFrame) -> ! {
+    writeln!(Console, "{}", FaultInfo::read(frame)).ok();
+
+    crate::exit(1)
+}
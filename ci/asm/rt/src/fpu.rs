@@ -0,0 +1,14 @@
+use core::{arch::asm, ptr};
+
+// Coprocessor Access Control Register
+const CPACR: *mut u32 = 0xE000_ED88 as *mut u32;
+
+/// Grants full access to the FPU (coprocessors CP10 and CP11)
+///
+/// Until this runs any floating point instruction triggers a UsageFault (escalated to HardFault)
+pub(crate) unsafe fn enable() {
+    ptr::write_volatile(CPACR, ptr::read_volatile(CPACR) | (0xF << 20));
+
+    // make sure the FPU is enabled before the next instruction executes
+    asm!("dsb", "isb");
+}
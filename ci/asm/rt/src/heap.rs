@@ -0,0 +1,29 @@
+/// Returns the start address of the heap
+///
+/// The heap is the RAM that's left after all the `static` variables have been allocated. `rt` doesn't
+/// provide a memory allocator; use this and `heap_end` to initialize one, e.g.
+/// `linked_list_allocator`:
+///
+/// ``` ignore
+/// let size = heap_end() - heap_start();
+/// unsafe { ALLOCATOR.lock().init(heap_start() as *mut u8, size) }
+/// ```
+///
+/// The returned address is 4-byte aligned.
+pub fn heap_start() -> usize {
+    extern "C" {
+        static _sheap: u8;
+    }
+
+    unsafe { &_sheap as *const u8 as usize }
+}
+
+/// Returns the end address (exclusive) of the heap: the initial stack pointer, `_stack_start`
+///
+/// `_stack_start` is the end of RAM unless the application overrides it; see `link.x`.
+///
+/// NOTE the heap shares this region with the call stack, which grows downwards from
+/// `_stack_start`. Leave enough space for the stack when sizing the heap.
+pub fn heap_end() -> usize {
+    crate::sections::stack_start()
+}
@@ -1,9 +1,17 @@
 #![no_std]
 
+#[cfg(not(any(feature = "panic-persist", feature = "panic-log", feature = "no-panic")))]
 use core::panic::PanicInfo;
 // use core::ptr;
 
-#[no_mangle]
+// with the `ram-image` or `sanitize-control` feature this is called by the `Reset` trampoline
+// defined in `ram_image` or `sanitize_control`
+#[cfg_attr(not(any(feature = "ram-image", feature = "sanitize-control")), no_mangle)]
+#[cfg_attr(
+    any(feature = "ram-image", feature = "sanitize-control"),
+    export_name = "__reset",
+    allow(non_snake_case)
+)]
 pub unsafe extern "C" fn Reset() -> ! {
     // Omitted to simplify the `objdump` output
     // Initialize RAM
@@ -22,6 +30,31 @@ pub unsafe extern "C" fn Reset() -> ! {
     // let count = &_edata as *const u8 as usize - &_sdata as *const u8 as usize;
     // ptr::copy_nonoverlapping(&_sidata as *const u8, &mut _sdata as *mut u8, count);
 
+    // NOTE this must run before anything that could clear the reset cause flags
+    #[cfg(feature = "reset-reason")]
+    reset_reason::capture();
+
+    #[cfg(feature = "set-vtor")]
+    set_vtor::set();
+
+    #[cfg(feature = "paint-stack")]
+    stack::paint();
+
+    #[cfg(feature = "stack-guard")]
+    stack_guard::paint();
+
+    #[cfg(feature = "verify-checksum")]
+    checksum::verify();
+
+    #[cfg(feature = "ramfunc")]
+    ramfunc::copy();
+
+    #[cfg(feature = "fpu")]
+    fpu::enable();
+
+    #[cfg(feature = "vtor-ram")]
+    vtor_ram::relocate();
+
     // Call user entry point
     extern "Rust" {
         fn main() -> !;
@@ -31,10 +64,24 @@ pub unsafe extern "C" fn Reset() -> ! {
 }
 
 // The reset vector, a pointer into the reset handler
+#[cfg(not(any(feature = "ram-image", feature = "sanitize-control")))]
 #[link_section = ".vector_table.reset_vector"]
 #[no_mangle]
 pub static RESET_VECTOR: unsafe extern "C" fn() -> ! = Reset;
 
+// The reset vector, a pointer into the trampoline that sets up MSP and VTOR
+#[cfg(feature = "ram-image")]
+#[link_section = ".vector_table.reset_vector"]
+#[no_mangle]
+pub static RESET_VECTOR: unsafe extern "C" fn() -> ! = ram_image::Reset;
+
+// The reset vector, a pointer into the trampoline that clears the CONTROL register
+#[cfg(feature = "sanitize-control")]
+#[link_section = ".vector_table.reset_vector"]
+#[no_mangle]
+pub static RESET_VECTOR: unsafe extern "C" fn() -> ! = sanitize_control::Reset;
+
+#[cfg(not(any(feature = "panic-persist", feature = "panic-log", feature = "no-panic")))]
 #[panic_handler]
 fn panic(_panic: &PanicInfo<'_>) -> ! {
     loop {}
@@ -42,13 +89,22 @@ fn panic(_panic: &PanicInfo<'_>) -> ! {
 
 #[macro_export]
 macro_rules! entry {
-    ($path:path) => {
+    // NOTE `tt`s, unlike a `path` fragment, keep their spans so errors point at the user's `main`
+    ($($path:tt)+) => {
         #[export_name = "main"]
         pub unsafe fn __main() -> ! {
-            // type check the given path
-            let f: fn() -> ! = $path;
+            // `main` must be a `fn() -> !`: no arguments, no type parameters and it must diverge
+            const MAIN: fn() -> ! = $($path)+;
 
-            f()
+            MAIN()
+        }
+
+        // a second `entry!` invocation anywhere in the crate is rejected with "the name
+        // `__entry_point_defined_multiple_times` is defined multiple times"
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! __entry_point_defined_multiple_times {
+            () => {};
         }
     }
 }
@@ -92,5 +148,394 @@ pub static EXCEPTIONS: [Vector; 14] = [
 
 #[no_mangle]
 pub extern "C" fn DefaultExceptionHandler() {
+    #[cfg(feature = "exception-number")]
+    exception_number::capture();
+
     loop {}
 }
+
+/// Registers stacked (pushed onto the stack) by the hardware on exception entry
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct ExceptionFrame {
+    pub r0: u32,
+    pub r1: u32,
+    pub r2: u32,
+    pub r3: u32,
+    pub r12: u32,
+    pub lr: u32,
+    pub pc: u32,
+    pub xpsr: u32,
+}
+
+/// Best-effort backtrace: stores up to `out.len()` return addresses, starting from the one in
+/// `frame.lr`, into `out` and returns how many were found
+///
+/// NOTE this is a heuristic. Without unwind tables there's no way to tell return addresses apart
+/// from other data, so this scans the stack above `frame` for words that *look* like return
+/// addresses: they point into `.text` and have their Thumb bit set. Expect some false positives.
+pub fn backtrace(frame: &ExceptionFrame, out: &mut [usize]) -> usize {
+    extern "C" {
+        static _stext: u8;
+        static _etext: u8;
+        static _stack_start: u8;
+    }
+
+    let (stext, etext, stack_start) = unsafe {
+        (
+            &_stext as *const u8 as usize,
+            &_etext as *const u8 as usize,
+            &_stack_start as *const u8 as usize,
+        )
+    };
+    let is_return_address = |addr: usize| addr & 1 == 1 && addr > stext && addr <= etext;
+
+    let mut n = 0;
+    if n < out.len() && is_return_address(frame.lr as usize) {
+        out[n] = frame.lr as usize;
+        n += 1;
+    }
+
+    // walk the stack upwards, starting right above the stacked registers
+    let mut sp = frame as *const ExceptionFrame as usize + core::mem::size_of::<ExceptionFrame>();
+    while n < out.len() && sp < stack_start {
+        let word = unsafe { core::ptr::read_volatile(sp as *const u32) } as usize;
+
+        if is_return_address(word) {
+            out[n] = word;
+            n += 1;
+        }
+
+        sp += 4;
+    }
+
+    n
+}
+
+/// Core exceptions whose handler can be overridden using `exception!`
+#[allow(non_camel_case_types)]
+pub enum Exception {
+    NMI,
+    MemManage,
+    BusFault,
+    UsageFault,
+    SVCall,
+    PendSV,
+    SysTick,
+}
+
+/// Overrides the handler of an exception
+///
+/// ``` ignore
+/// exception!(SysTick, tick);
+///
+/// fn tick() {
+///     // ..
+/// }
+/// ```
+///
+/// The handler of `HardFault` receives the registers stacked by the hardware and must not return:
+/// its signature is `fn(&ExceptionFrame) -> !`. All the other handlers have signature `fn()`. Using
+/// a name that's not listed in `Exception`, or `HardFault`, is a compile error.
+#[macro_export]
+macro_rules! exception {
+    (HardFault, $path:path) => {
+        #[export_name = "HardFault"]
+        pub unsafe extern "C" fn __HardFault(ef: &$crate::ExceptionFrame) -> ! {
+            // type check the given path
+            let f: fn(&$crate::ExceptionFrame) -> ! = $path;
+
+            f(ef)
+        }
+    };
+
+    ($name:ident, $path:path) => {
+        #[allow(non_snake_case)]
+        #[no_mangle]
+        pub unsafe extern "C" fn $name() {
+            // check that this is a valid exception name
+            let _ = $crate::Exception::$name;
+
+            // type check the given path
+            let f: fn() = $path;
+
+            f()
+        }
+    };
+}
+
+impl Vector {
+    /// Creates a vector that points to the given `handler`
+    pub const fn new(handler: unsafe extern "C" fn()) -> Self {
+        Vector { handler }
+    }
+}
+
+// The hardware reads the vector table as an array of 32-bit words; a `Vector` of any other size or
+// alignment would silently shift all the entries that come after it
+const _: () = core::assert!(core::mem::size_of::<Vector>() == 4);
+const _: () = core::assert!(core::mem::align_of::<Vector>() == 4);
+
+/// Declares the device specific interrupts and their position in the vector table
+///
+/// This is meant to be used by device crates:
+///
+/// ``` ignore
+/// // this device has 32 interrupts; the handler of TIM2 goes in the 6th slot
+/// rt::interrupts!(32; 5 => TIM2, 10 => UART0);
+/// ```
+///
+/// This puts a table with `$n` vectors right after the core exception vectors and an `Interrupt`
+/// enum with a variant per named interrupt in the calling module. The unnamed slots as well as the
+/// named interrupts that the application doesn't handle (see `interrupt!`) point to
+/// `DefaultExceptionHandler`.
+///
+/// `$n` must be an integer literal: it's also exported as the `NUM_INTERRUPTS` symbol, which
+/// `link.x` uses to check the size of the vector table.
+#[macro_export]
+macro_rules! interrupts {
+    ($n:literal; $($i:expr => $name:ident),* $(,)?) => {
+        /// Device specific interrupts
+        #[allow(non_camel_case_types)]
+        pub enum Interrupt {
+            $($name,)*
+        }
+
+        extern "C" {
+            $(fn $name();)*
+        }
+
+        #[link_section = ".vector_table.interrupts"]
+        #[no_mangle]
+        pub static __INTERRUPTS: [$crate::Vector; $n] = {
+            const DEFAULT: $crate::Vector = $crate::Vector::new($crate::DefaultExceptionHandler);
+
+            let mut vectors = [DEFAULT; $n];
+            $(vectors[$i] = $crate::Vector::new($name);)*
+            vectors
+        };
+
+        // weak aliases: the handlers defined by the application take precedence
+        core::arch::global_asm!(
+            ".pushsection .text.__DefaultInterruptHandler,\"ax\",%progbits",
+            ".thumb_func",
+            "__DefaultInterruptHandler:",
+            "b DefaultExceptionHandler",
+            $(
+                concat!(".weak ", stringify!($name)),
+                concat!(".thumb_set ", stringify!($name), ", __DefaultInterruptHandler"),
+            )*
+            ".popsection",
+            // checked against the size of the vector table in `link.x`
+            ".global NUM_INTERRUPTS",
+            concat!(".set NUM_INTERRUPTS, ", stringify!($n)),
+        );
+    };
+}
+
+/// Sets the handler of a device specific interrupt
+///
+/// ``` ignore
+/// use device::Interrupt;
+///
+/// interrupt!(TIM2, tick);
+///
+/// fn tick() {
+///     // ..
+/// }
+/// ```
+///
+/// The `Interrupt` enum generated by the device crate's `interrupts!` call must be in scope; using
+/// a name that's not one of its variants is a compile error.
+#[macro_export]
+macro_rules! interrupt {
+    ($name:ident, $path:path) => {
+        #[allow(non_snake_case)]
+        #[no_mangle]
+        pub unsafe extern "C" fn $name() {
+            // check that this is a valid interrupt name
+            let _ = Interrupt::$name;
+
+            // type check the given path
+            let f: fn() = $path;
+
+            f()
+        }
+    };
+}
+
+/// Sets the handler of an exception or device specific interrupt and declares the `static`
+/// variables that the handler shares with the rest of the program
+///
+/// ``` ignore
+/// use core::sync::atomic::{AtomicU32, Ordering};
+///
+/// static TICKS: AtomicU32 = AtomicU32::new(0);
+///
+/// bind_interrupt!(SysTick, tick, resources = [TICKS]);
+///
+/// fn tick() {
+///     TICKS.fetch_add(1, Ordering::Relaxed);
+/// }
+/// ```
+///
+/// Each resource must be a `static` or `static mut` variable whose type is both `Send` and `Sync`:
+/// the handler runs in interrupt context so anything it shares with `main` (or other handlers)
+/// crosses execution contexts. Listing a resource of a type like `Cell<u32>` is a compile error.
+///
+/// `$name` is checked as in `exception!` (`HardFault` is not supported) or, if it's not a core
+/// exception, as in `interrupt!`.
+#[macro_export]
+macro_rules! bind_interrupt {
+    ($name:ident, $path:path, resources = [$($resource:path),* $(,)?]) => {
+        $crate::__bind_handler!($name, $path);
+
+        const _: () = {
+            #[allow(dead_code)]
+            unsafe fn resources() {
+                $($crate::__assert_resource(core::ptr::addr_of!($resource));)*
+            }
+        };
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bind_handler {
+    (NMI, $path:path) => { $crate::exception!(NMI, $path); };
+    (MemManage, $path:path) => { $crate::exception!(MemManage, $path); };
+    (BusFault, $path:path) => { $crate::exception!(BusFault, $path); };
+    (UsageFault, $path:path) => { $crate::exception!(UsageFault, $path); };
+    (SVCall, $path:path) => { $crate::exception!(SVCall, $path); };
+    (PendSV, $path:path) => { $crate::exception!(PendSV, $path); };
+    (SysTick, $path:path) => { $crate::exception!(SysTick, $path); };
+    ($name:ident, $path:path) => { $crate::interrupt!($name, $path); };
+}
+
+#[doc(hidden)]
+pub fn __assert_resource<T>(_: *const T)
+where
+    T: Send + Sync + ?Sized,
+{
+}
+
+mod exit;
+mod heap;
+
+pub use exit::exit;
+pub use heap::{heap_end, heap_start};
+
+pub mod sections;
+
+/// Attributes
+pub mod attr {
+    /// Marks the entry point of the program
+    ///
+    /// ``` ignore
+    /// use rt::attr::entry;
+    ///
+    /// #[entry]
+    /// fn main() -> ! {
+    ///     loop {}
+    /// }
+    /// ```
+    ///
+    /// The function must have signature `fn() -> !`: no arguments, no type parameters, not `async`
+    /// and it must diverge. There can only be one entry point per program.
+    ///
+    /// NOTE this lives in the `attr` module because `rt::entry` is the `entry!` macro, which will be
+    /// removed in the next release
+    pub use macros::entry;
+}
+
+#[cfg(feature = "panic-persist")]
+mod panic_persist;
+
+#[cfg(feature = "panic-persist")]
+pub use panic_persist::take_panic_message;
+
+#[cfg(feature = "panic-log")]
+mod panic_log;
+
+#[cfg(feature = "boot-mode")]
+mod boot_mode;
+
+#[cfg(feature = "boot-mode")]
+pub use boot_mode::{boot_mode, BootMode};
+
+#[cfg(feature = "vtor-ram")]
+mod vtor_ram;
+
+#[cfg(feature = "vtor-ram")]
+pub use vtor_ram::set_interrupt_handler;
+
+#[cfg(feature = "fpu")]
+mod fpu;
+
+#[cfg(feature = "paint-stack")]
+mod stack;
+
+#[cfg(feature = "paint-stack")]
+pub use stack::stack_used;
+
+#[cfg(feature = "exception-number")]
+mod exception_number;
+
+#[cfg(feature = "exception-number")]
+pub use exception_number::EXCEPTION_NUMBER;
+
+#[cfg(feature = "stack-guard")]
+mod stack_guard;
+
+#[cfg(feature = "stack-guard")]
+pub use stack_guard::stack_overflowed;
+
+#[cfg(feature = "verify-checksum")]
+mod checksum;
+
+#[cfg(feature = "verify-checksum")]
+pub use checksum::image_crc;
+
+#[cfg(feature = "ramfunc")]
+mod ramfunc;
+
+#[cfg(feature = "ramfunc")]
+pub use macros::ramfunc;
+
+#[cfg(feature = "ram-image")]
+mod ram_image;
+
+#[cfg(feature = "no-panic")]
+mod no_panic;
+
+#[cfg(feature = "fault-report")]
+mod fault_report;
+
+#[cfg(feature = "fault-report")]
+pub use fault_report::FaultInfo;
+
+#[cfg(feature = "sanitize-control")]
+mod sanitize_control;
+
+#[cfg(feature = "set-vtor")]
+mod set_vtor;
+
+#[cfg(feature = "reset-reason")]
+mod reset_reason;
+
+#[cfg(feature = "reset-reason")]
+pub use reset_reason::{reset_reason, ResetReason};
+
+#[cfg(feature = "watchdog")]
+mod watchdog;
+
+#[cfg(feature = "watchdog")]
+pub use watchdog::IndependentWatchdog;
+
+// NOTE `assert!` is exported at the root of this crate: use `core::assert!` in this crate
+#[cfg(feature = "assert-log")]
+mod assert;
+
+#[cfg(feature = "assert-log")]
+#[doc(hidden)]
+pub use log as __log;
@@ -0,0 +1,11 @@
+use core::panic::PanicInfo;
+
+extern "C" {
+    // NOTE this symbol is never defined: linking fails if the panic handler is reachable
+    fn rust_panic_is_reachable() -> !;
+}
+
+#[panic_handler]
+fn panic(_panic: &PanicInfo<'_>) -> ! {
+    unsafe { rust_panic_is_reachable() }
+}
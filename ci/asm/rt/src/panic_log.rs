@@ -0,0 +1,30 @@
+use core::{
+    panic::PanicInfo,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use log::{error, flush};
+
+static PANICKING: AtomicBool = AtomicBool::new(false);
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    // if logging panics we end up here again; don't try to log a second time
+    if !PANICKING.swap(true, Ordering::Relaxed) {
+        if let Some(location) = info.location() {
+            // NOTE the file name is not a literal so it can't be interned; instead we send the
+            // address of the string, which the host can look up in the ELF file
+            error!(
+                "panicked at line {} of {}",
+                location.line(),
+                location.file().as_ptr() as u32
+            );
+        } else {
+            error!("panicked");
+        }
+
+        flush!();
+    }
+
+    loop {}
+}
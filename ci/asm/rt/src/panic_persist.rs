@@ -0,0 +1,92 @@
+use core::{
+    fmt::{self, Write},
+    panic::PanicInfo,
+    ptr, str,
+};
+
+// marks the contents of `PANIC_MESSAGE` as valid
+const MAGIC: u32 = 0xDEAD_C0DE;
+
+// longer messages are truncated
+const CAPACITY: usize = 128;
+
+struct Message {
+    magic: u32,
+    len: usize,
+    buffer: [u8; CAPACITY],
+}
+
+impl Write for Message {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut n = s.len().min(CAPACITY - self.len);
+        while !s.is_char_boundary(n) {
+            n -= 1;
+        }
+
+        self.buffer[self.len..self.len + n].copy_from_slice(&s.as_bytes()[..n]);
+        self.len += n;
+
+        Ok(())
+    }
+}
+
+// `Reset` doesn't initialize the `.uninit` section so this survives a reset
+#[link_section = ".uninit.PANIC_MESSAGE"]
+static mut PANIC_MESSAGE: Message = Message {
+    magic: 0,
+    len: 0,
+    buffer: [0; CAPACITY],
+};
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    unsafe {
+        let message = &mut *ptr::addr_of_mut!(PANIC_MESSAGE);
+
+        message.magic = 0;
+        message.len = 0;
+        // NOTE `PanicInfo::message` is not stable on the oldest toolchain we support; store the
+        // location of the panic instead
+        if let Some(location) = info.location() {
+            write!(message, "panicked at {}", location).ok();
+        } else {
+            message.write_str("panicked").ok();
+        }
+        message.magic = MAGIC;
+
+        extern "C" {
+            fn PanicReset() -> !;
+        }
+
+        PanicReset()
+    }
+}
+
+// Waits for the watchdog, if enabled, to reset the device; see `link.x`
+#[no_mangle]
+pub extern "C" fn DefaultPanicReset() -> ! {
+    loop {}
+}
+
+/// Returns the message of the panic that preceded the last reset, if any, and clears it so that
+/// later calls return `None`
+///
+/// The message has the form `panicked at $file:$line:$column`. The panic handler stores its first
+/// 128 bytes in the `.uninit` section and then calls `PanicReset`, which spins until the watchdog
+/// resets the device; override it to reset the device right away. This only works if the reset
+/// preserves the contents of RAM, e.g. a watchdog or software reset; after a power-on reset this
+/// returns `None`.
+///
+/// # Safety
+///
+/// This must not be called while another call to it may be running, e.g. from an interrupt handler.
+pub unsafe fn take_panic_message() -> Option<&'static str> {
+    let message = &mut *ptr::addr_of_mut!(PANIC_MESSAGE);
+
+    if message.magic != MAGIC || message.len > CAPACITY {
+        return None;
+    }
+
+    message.magic = 0;
+    str::from_utf8(&message.buffer[..message.len]).ok()
+}
@@ -0,0 +1,34 @@
+//! Support for images that a debugger loads straight into RAM
+//!
+//! When the image is not programmed into Flash the hardware doesn't load the initial stack pointer
+//! from the vector table and VTOR keeps pointing to the (empty) Flash; the debugger only jumps to
+//! the ELF entry point. This feature turns `Reset` into a trampoline that sets up the Main Stack
+//! Pointer (MSP) and VTOR before any Rust code, which may use the stack, runs.
+
+use core::arch::global_asm;
+
+// NOTE ARMv6-M compatible: only low registers, no `b` to far away symbols
+global_asm!(
+    ".pushsection .text.Reset,\"ax\",%progbits",
+    ".global Reset",
+    ".thumb_func",
+    "Reset:",
+    // initial stack pointer
+    "ldr r0, =_stack_start",
+    "msr MSP, r0",
+    // Vector Table Offset Register: the vector table that was loaded into RAM
+    "ldr r0, =_svector_table",
+    "ldr r1, =0xE000ED08",
+    "str r0, [r1]",
+    "dsb",
+    "isb",
+    // continue with the Rust part of the reset handler
+    "ldr r0, =__reset",
+    "bx r0",
+    ".popsection",
+);
+
+extern "C" {
+    // the trampoline defined above
+    pub(crate) fn Reset() -> !;
+}
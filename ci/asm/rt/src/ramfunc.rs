@@ -0,0 +1,17 @@
+use core::{arch::asm, ptr};
+
+/// Copies the `.ram_text` section from Flash into RAM
+pub(crate) unsafe fn copy() {
+    extern "C" {
+        static mut _sram_text: u32;
+        static mut _eram_text: u32;
+        static _siram_text: u32;
+    }
+
+    let start = ptr::addr_of_mut!(_sram_text);
+    let count = (ptr::addr_of!(_eram_text) as usize - start as usize) / 4;
+    ptr::copy_nonoverlapping(ptr::addr_of!(_siram_text), start, count);
+
+    // make sure the copy is complete before any of the copied instructions is fetched
+    asm!("dsb", "isb", options(nostack, preserves_flags));
+}
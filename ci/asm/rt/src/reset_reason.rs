@@ -0,0 +1,58 @@
+use core::ptr;
+
+/// Cause of the last reset
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(u8)]
+pub enum ResetReason {
+    /// The device was powered on
+    PowerOn,
+    /// The supply voltage dropped below the brown-out threshold
+    BrownOut,
+    /// The external reset pin was asserted
+    External,
+    /// The watchdog timed out; see `IndependentWatchdog`
+    Watchdog,
+    /// The software requested a reset, e.g. through `SCB.AIRCR.SYSRESETREQ`
+    Software,
+    /// The cause couldn't be determined
+    Unknown,
+}
+
+// The reason reported by `__reset_reason`; `Reset` doesn't initialize the `.uninit` section so this
+// is not overwritten by the RAM initialization
+#[link_section = ".uninit.RESET_REASON"]
+static mut RESET_REASON: ResetReason = ResetReason::Unknown;
+
+/// Returns the cause of the last reset
+///
+/// How to tell the reset cause is chip-specific (e.g. the reset cause register, RESC, of the
+/// LM3S6965) so the default implementation is a stub that always reports `ResetReason::Unknown`.
+/// Override it by defining this function:
+///
+/// ``` ignore
+/// #[no_mangle]
+/// pub extern "C" fn __reset_reason() -> ResetReason {
+///     // .. read (and clear) the reset cause flags ..
+/// }
+/// ```
+///
+/// `Reset` calls `__reset_reason` once, before it initializes the RAM, and stores its result;
+/// this function returns the stored value.
+pub fn reset_reason() -> ResetReason {
+    unsafe { ptr::read_volatile(ptr::addr_of!(RESET_REASON)) }
+}
+
+// called by `Reset` before anything else
+pub(crate) unsafe fn capture() {
+    extern "C" {
+        fn __reset_reason() -> ResetReason;
+    }
+
+    ptr::write_volatile(ptr::addr_of_mut!(RESET_REASON), __reset_reason());
+}
+
+// The default `__reset_reason`; see `link.x`
+#[no_mangle]
+pub extern "C" fn DefaultResetReason() -> ResetReason {
+    ResetReason::Unknown
+}
@@ -0,0 +1,33 @@
+//! Deterministic start up after a jump to `Reset`
+//!
+//! A hardware reset always leaves the processor in Thread mode, using the Main Stack Pointer (MSP),
+//! with privileged access. A bootloader, or a previous firmware, that jumps to `Reset` may leave
+//! the CONTROL register with a different configuration, e.g. using the Process Stack Pointer (PSP).
+//! This feature turns `Reset` into a trampoline that clears CONTROL before any Rust code, which may
+//! use the stack, runs.
+//!
+//! NOTE writes to CONTROL are ignored in unprivileged Thread mode so this can't give back the
+//! privileges; a bootloader must jump to `Reset` while privileged, e.g. from an exception handler.
+
+use core::arch::global_asm;
+
+// NOTE ARMv6-M compatible: only low registers, no `b` to far away symbols
+global_asm!(
+    ".pushsection .text.Reset,\"ax\",%progbits",
+    ".global Reset",
+    ".thumb_func",
+    "Reset:",
+    // CONTROL.SPSEL = 0 (use MSP), CONTROL.nPRIV = 0 (privileged) and, on ARMv7E-M, CONTROL.FPCA = 0
+    "movs r0, #0",
+    "msr CONTROL, r0",
+    "isb",
+    // continue with the Rust part of the reset handler
+    "ldr r0, =__reset",
+    "bx r0",
+    ".popsection",
+);
+
+extern "C" {
+    // the trampoline defined above
+    pub(crate) fn Reset() -> !;
+}
@@ -0,0 +1,59 @@
+//! Boundaries of the memory sections defined in `link.x`
+//!
+//! All the addresses are 4-byte aligned. The end addresses are exclusive.
+
+/// Start address of the `.bss` section (`_sbss`)
+pub fn sbss() -> usize {
+    extern "C" {
+        static _sbss: u8;
+    }
+
+    unsafe { &_sbss as *const u8 as usize }
+}
+
+/// End address of the `.bss` section (`_ebss`)
+pub fn ebss() -> usize {
+    extern "C" {
+        static _ebss: u8;
+    }
+
+    unsafe { &_ebss as *const u8 as usize }
+}
+
+/// Start address of the `.data` section in RAM (`_sdata`)
+pub fn sdata() -> usize {
+    extern "C" {
+        static _sdata: u8;
+    }
+
+    unsafe { &_sdata as *const u8 as usize }
+}
+
+/// End address of the `.data` section in RAM (`_edata`)
+pub fn edata() -> usize {
+    extern "C" {
+        static _edata: u8;
+    }
+
+    unsafe { &_edata as *const u8 as usize }
+}
+
+/// Address of the initial values of the `.data` section in Flash (`_sidata`)
+pub fn sidata() -> usize {
+    extern "C" {
+        static _sidata: u8;
+    }
+
+    unsafe { &_sidata as *const u8 as usize }
+}
+
+/// Initial value of the stack pointer (`_stack_start`)
+///
+/// This is the end of RAM unless the application overrides it; see `link.x`.
+pub fn stack_start() -> usize {
+    extern "C" {
+        static _stack_start: u8;
+    }
+
+    unsafe { &_stack_start as *const u8 as usize }
+}
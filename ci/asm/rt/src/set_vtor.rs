@@ -0,0 +1,19 @@
+use core::{arch::asm, ptr};
+
+// Vector Table Offset Register
+const VTOR: *mut u32 = 0xE000_ED08 as *mut u32;
+
+/// Points VTOR to the vector table of this image
+///
+/// VTOR resets to 0 so this is required when the image doesn't start at address 0, e.g. when it
+/// lives behind a bootloader
+pub(crate) unsafe fn set() {
+    extern "C" {
+        static _svector_table: u8;
+    }
+
+    ptr::write_volatile(VTOR, &_svector_table as *const u8 as u32);
+
+    // the new vector table must be in use before any exception is taken
+    asm!("dsb", "isb", options(nomem, nostack, preserves_flags));
+}
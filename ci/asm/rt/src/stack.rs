@@ -0,0 +1,42 @@
+use core::{arch::asm, ptr};
+
+use crate::{heap_end, heap_start};
+
+// the stack can grow all the way down to the start of the heap; unused words hold this value
+const PATTERN: u32 = 0xAAAA_AAAA;
+
+/// Fills the unused part of the stack, from the start of the heap to the stack pointer, with
+/// `PATTERN`
+pub(crate) unsafe fn paint() {
+    // NOTE this is written in assembly because the loop must not use the stack: it overwrites
+    // everything below the stack pointer
+    asm!(
+        "mov {end}, sp",
+        "0:",
+        "cmp {start}, {end}",
+        "bhs 1f",
+        "str {pattern}, [{start}]",
+        "adds {start}, #4",
+        "b 0b",
+        "1:",
+        start = inout(reg) heap_start() => _,
+        end = out(reg) _,
+        pattern = in(reg) PATTERN,
+        options(nostack),
+    );
+}
+
+/// Returns the maximum number of bytes of stack used so far (high-water mark)
+///
+/// This scans the painted region from the bottom for the first word that has been overwritten so
+/// the result is an estimate: it's off by a few bytes if the stack happened to contain `PATTERN`.
+///
+/// NOTE this assumes that nothing else, e.g. a heap allocator, uses the RAM past `.bss`
+pub fn stack_used() -> usize {
+    let mut addr = heap_start();
+    while addr < heap_end() && unsafe { ptr::read_volatile(addr as *const u32) } == PATTERN {
+        addr += 4;
+    }
+
+    heap_end() - addr
+}
@@ -0,0 +1,53 @@
+use core::ptr;
+
+// unused words of the guard region hold this value
+const PATTERN: u32 = 0xDEAD_BEEF;
+
+// boundaries of the guard region; see `link.x`
+fn guard() -> (usize, usize) {
+    extern "C" {
+        static _sstack_guard: u32;
+        static _estack_guard: u32;
+    }
+
+    unsafe {
+        (
+            &_sstack_guard as *const u32 as usize,
+            &_estack_guard as *const u32 as usize,
+        )
+    }
+}
+
+/// Fills the stack guard region with `PATTERN`
+pub(crate) unsafe fn paint() {
+    let (start, end) = guard();
+
+    let mut addr = start;
+    while addr < end {
+        ptr::write_volatile(addr as *mut u32, PATTERN);
+        addr += 4;
+    }
+}
+
+/// Returns `true` if the stack has grown into the guard region that sits between the static
+/// variables and the heap / stack space
+///
+/// This is checked after the fact: by the time this returns `true` the stack has already corrupted
+/// the guard region and, potentially, the static variables below it. An overflow that skips over
+/// the guard region without writing to it (e.g. a large stack frame) goes undetected.
+///
+/// NOTE the size of the guard region is set with `-C link-arg=--defsym=_stack_guard_size=N`; with
+/// the default size of 0 there's no guard region and this always returns `false`
+pub fn stack_overflowed() -> bool {
+    let (start, end) = guard();
+
+    let mut addr = start;
+    while addr < end {
+        if unsafe { ptr::read_volatile(addr as *const u32) } != PATTERN {
+            return true;
+        }
+        addr += 4;
+    }
+
+    false
+}
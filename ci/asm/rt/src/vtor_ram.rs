@@ -0,0 +1,68 @@
+use core::{arch::asm, ptr};
+
+use crate::DefaultExceptionHandler;
+
+// Vector Table Offset Register
+const VTOR: *mut u32 = 0xE000_ED08 as *mut u32;
+
+// 16 core exceptions + up to 240 device specific interrupts
+const N: usize = 256;
+
+// VTOR requires the table to be aligned to its size rounded up to the next power of two
+#[repr(C, align(1024))]
+struct RamVectors([usize; N]);
+
+#[link_section = ".ram_vectors"]
+static mut RAM_VECTORS: RamVectors = RamVectors([0; N]);
+
+/// Copies the vector table into RAM and points VTOR to the copy
+///
+/// The slots that the Flash vector table doesn't cover are set to `DefaultExceptionHandler`
+pub(crate) unsafe fn relocate() {
+    extern "C" {
+        static _svector_table: u8;
+        static _evector_table: u8;
+        static _stack_start: u8;
+    }
+
+    let start = &_svector_table as *const u8 as usize;
+    let count = (&_evector_table as *const u8 as usize - start) / 4;
+    let ram = ptr::addr_of_mut!(RAM_VECTORS) as *mut usize;
+
+    // NOTE the first entry, the initial stack pointer, is not read from Flash because the vector table
+    // usually lives at address 0
+    ptr::write_volatile(ram, &_stack_start as *const u8 as usize);
+    for i in 1..N {
+        let vector = if i < count {
+            ptr::read_volatile((start + 4 * i) as *const usize)
+        } else {
+            DefaultExceptionHandler as extern "C" fn() as usize
+        };
+
+        ptr::write_volatile(ram.add(i), vector);
+    }
+
+    ptr::write_volatile(VTOR, ram as u32);
+
+    // make sure the new vector table is used from this point on
+    asm!("dsb", "isb");
+}
+
+/// Makes `handler` the handler of the `n`-th device specific interrupt
+///
+/// The vector table is relocated to RAM by `Reset` so this can be used to change interrupt handlers
+/// at runtime.
+///
+/// # Safety
+///
+/// The interrupt must be disabled (or not pending) while its handler is being changed.
+///
+/// # Panics
+///
+/// This function panics if `n` is greater than 239, the maximum number of interrupts
+pub unsafe fn set_interrupt_handler(n: usize, handler: extern "C" fn()) {
+    core::assert!(n < N - 16);
+
+    let ram = ptr::addr_of_mut!(RAM_VECTORS) as *mut usize;
+    ptr::write_volatile(ram.add(16 + n), handler as usize);
+}
@@ -0,0 +1,57 @@
+use core::ptr;
+
+// Run-Mode Clock Gating Control Register 0
+const RCGC0: *mut u32 = 0x400F_E100 as *mut u32;
+const RCGC0_WDT: u32 = 1 << 3;
+
+// Watchdog Timer 0 registers
+const WDTLOAD: *mut u32 = 0x4000_0000 as *mut u32;
+const WDTCTL: *mut u32 = 0x4000_0008 as *mut u32;
+const WDTICR: *mut u32 = 0x4000_000C as *mut u32;
+const WDTLOCK: *mut u32 = 0x4000_0C00 as *mut u32;
+
+// WDTCTL: interrupt enable, which also starts the counter, and reset enable
+const INTEN: u32 = 1 << 0;
+const RESEN: u32 = 1 << 1;
+
+// writing this value to WDTLOCK unlocks the other registers; any other value locks them
+const UNLOCK: u32 = 0x1ACC_E551;
+
+/// A watchdog that resets the device unless it's fed in time
+///
+/// NOTE LM3S6965 specific: this is the Watchdog Timer 0. Once started it can only be stopped by a
+/// reset
+pub struct IndependentWatchdog {
+    timeout: u32,
+}
+
+impl IndependentWatchdog {
+    /// Starts the watchdog
+    ///
+    /// The counter runs at the system clock frequency. On the first time out, after `timeout`
+    /// cycles, the watchdog raises an interrupt and reloads the counter; on the second one it
+    /// resets the device. `feed` must be called at least once every `timeout` cycles.
+    pub fn start(timeout: u32) -> Self {
+        unsafe {
+            let rcgc0 = ptr::read_volatile(RCGC0);
+            ptr::write_volatile(RCGC0, rcgc0 | RCGC0_WDT);
+
+            ptr::write_volatile(WDTLOCK, UNLOCK);
+            ptr::write_volatile(WDTLOAD, timeout);
+            ptr::write_volatile(WDTCTL, INTEN | RESEN);
+            ptr::write_volatile(WDTLOCK, 0);
+        }
+
+        IndependentWatchdog { timeout }
+    }
+
+    /// Restarts the count down from `timeout`
+    pub fn feed(&mut self) {
+        unsafe {
+            ptr::write_volatile(WDTLOCK, UNLOCK);
+            // any write to WDTICR reloads the counter with the value of WDTLOAD
+            ptr::write_volatile(WDTICR, self.timeout);
+            ptr::write_volatile(WDTLOCK, 0);
+        }
+    }
+}
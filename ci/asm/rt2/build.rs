@@ -6,6 +6,8 @@ use std::{
     path::PathBuf,
 };
 
+use cc::Build;
+
 fn main() -> Result<(), Box<dyn Error>> {
     // build directory for this crate
     let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
@@ -16,12 +18,18 @@ fn main() -> Result<(), Box<dyn Error>> {
     // put `link.x` in the build directory
     File::create(out_dir.join("link.x"))?.write_all(include_bytes!("link.x"))?;
 
-    // link to `librt.a`
-    fs::copy("librt.a", out_dir.join("librt.a"))?; // <- NEW!
-    println!("cargo:rustc-link-lib=static=rt"); // <- NEW!
+    // assemble `asm.s` if there's an ARM toolchain around; this produces `libasm.a`
+    if Build::new().file("asm.s").try_compile("asm").is_err() {
+        // otherwise link to the pre-assembled `librt.a`
+        println!("cargo:warning=couldn't assemble `asm.s`; using the pre-assembled `librt.a`");
+
+        fs::copy("librt.a", out_dir.join("librt.a"))?;
+        println!("cargo:rustc-link-lib=static=rt");
+    }
 
-    // rebuild if `librt.a` changed
-    println!("cargo:rerun-if-changed=librt.a"); // <- NEW!
+    // rebuild if `asm.s` or `librt.a` changed
+    println!("cargo:rerun-if-changed=asm.s");
+    println!("cargo:rerun-if-changed=librt.a");
 
     Ok(())
 }
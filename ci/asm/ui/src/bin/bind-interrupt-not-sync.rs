@@ -0,0 +1,19 @@
+#![no_main]
+#![no_std]
+
+use core::cell::Cell;
+
+use rt::{bind_interrupt, entry};
+
+static mut TICKS: Cell<u32> = Cell::new(0);
+
+entry!(main);
+
+fn main() -> ! {
+    loop {}
+}
+
+// error: `Cell<u32>` cannot be shared between threads safely
+bind_interrupt!(SysTick, sys_tick, resources = [TICKS]);
+
+fn sys_tick() {}
@@ -0,0 +1,10 @@
+#![no_main]
+#![no_std]
+
+use rt::attr::entry;
+
+// error: the entry point can't be `async`
+#[entry]
+async fn main() -> ! {
+    loop {}
+}
@@ -0,0 +1,12 @@
+#![no_main]
+#![no_std]
+
+use rt::attr::entry;
+
+// error: the name `__entry_point_defined_multiple_times` is defined multiple times
+rt::entry!(main);
+
+#[entry]
+fn main() -> ! {
+    loop {}
+}
@@ -0,0 +1,10 @@
+#![no_main]
+#![no_std]
+
+use rt::attr::entry;
+
+// error: the entry point must have signature `fn() -> !`
+#[entry]
+fn main() -> u8 {
+    loop {}
+}
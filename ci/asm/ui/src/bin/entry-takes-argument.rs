@@ -0,0 +1,10 @@
+#![no_main]
+#![no_std]
+
+use rt::attr::entry;
+
+// error: the entry point can't take arguments
+#[entry]
+fn main(x: u32) -> ! {
+    loop {}
+}
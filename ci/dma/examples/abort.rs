@@ -0,0 +1,245 @@
+//! Aborting a transfer
+
+#![deny(missing_docs, warnings)]
+
+use core::{
+    hint,
+    marker::Unpin,
+    mem,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    ptr,
+    sync::atomic::{self, Ordering},
+};
+
+use as_slice::{AsMutSlice, AsSlice};
+use shared::{Dma1Channel1, MemoryAddress, Peripherals, USART1_RX, USART1_TX};
+
+/// A DMA transfer
+pub struct Transfer<B> {
+    // NOTE: always `Some` variant
+    inner: Option<Inner<B>>,
+}
+
+// NOTE: previously named `Transfer<B>`
+struct Inner<B> {
+    buffer: Pin<B>,
+    // number of bytes requested when the transfer started
+    len: usize,
+    serial: Serial1,
+}
+
+impl<B> Transfer<B> {
+    /// Blocks until the transfer is done and returns the buffer
+    pub fn wait(mut self) -> (Pin<B>, Serial1) {
+        while !self.is_done() {}
+
+        atomic::compiler_fence(Ordering::Acquire);
+
+        let inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+        (inner.buffer, inner.serial)
+    }
+
+    /// Stops the transfer and returns the buffer
+    ///
+    /// Use this to give up on a transfer that's taking too long. NOTE some of the data may have
+    /// been transferred
+    pub fn abort(mut self) -> (Pin<B>, Serial1) {
+        // NOTE taking `inner` out disarms the destructor so the DMA is only stopped once
+        let mut inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+
+        // NOTE: this is a volatile write
+        inner.serial.dma.stop();
+
+        // we need a read here to make the Acquire fence effective
+        unsafe {
+            ptr::read_volatile(&0);
+        }
+
+        // same reason as in `Transfer.wait`
+        atomic::compiler_fence(Ordering::Acquire);
+
+        (inner.buffer, inner.serial)
+    }
+
+    /// Returns the number of bytes that have been transferred so far
+    ///
+    /// NOTE the value keeps increasing while the transfer is in progress
+    pub fn bytes_transferred(&self) -> usize {
+        let inner = self
+            .inner
+            .as_ref()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+
+        inner.len - inner.serial.dma.remaining()
+    }
+
+    /// Returns `true` if the first half of the buffer has been transferred
+    ///
+    /// NOTE this keeps returning `true` after the whole transfer is done
+    pub fn is_half_done(&self) -> bool {
+        let inner = self
+            .inner
+            .as_ref()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+
+        inner.serial.dma.is_half_transfer_complete()
+    }
+}
+
+impl<B> Drop for Transfer<B> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.as_mut() {
+            // NOTE: this is a volatile write
+            inner.serial.dma.stop();
+
+            // we need a read here to make the Acquire fence effective
+            // we do *not* need this if `dma.stop` does a RMW operation
+            unsafe {
+                ptr::read_volatile(&0);
+            }
+
+            // we need a fence here for the same reason we need one in `Transfer.wait`
+            atomic::compiler_fence(Ordering::Acquire);
+        }
+    }
+}
+
+impl Serial1 {
+    /// Receives data into the given `buffer` until it's filled
+    ///
+    /// Returns a value that represents the in-progress DMA transfer
+    pub fn read_exact<B>(mut self, mut buffer: Pin<B>) -> Transfer<B>
+    where
+        B: DerefMut + 'static,
+        B::Target: AsMutSlice<Element = u8> + Unpin,
+    {
+        // .. same as before ..
+        let slice = buffer.as_mut_slice();
+        let (ptr, len) = (slice.as_mut_ptr(), slice.len());
+
+        self.dma.set_source_address(USART1_RX, false);
+        self.dma
+            .set_destination_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner {
+                buffer,
+                len,
+                serial: self,
+            }),
+        }
+    }
+
+    /// Sends out the given `buffer`
+    ///
+    /// Returns a value that represents the in-progress DMA transfer
+    pub fn write_all<B>(mut self, buffer: Pin<B>) -> Transfer<B>
+    where
+        B: Deref + 'static,
+        B::Target: AsSlice<Element = u8>,
+    {
+        // .. same as before ..
+        let slice = buffer.as_slice();
+        let (ptr, len) = (slice.as_ptr(), slice.len());
+
+        self.dma.set_destination_address(USART1_TX, false);
+        self.dma
+            .set_source_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner {
+                buffer,
+                len,
+                serial: self,
+            }),
+        }
+    }
+}
+
+#[allow(dead_code)]
+fn timeout(serial: Serial1) {
+    let buf = Pin::new(Box::new([0; 16]));
+
+    let t = serial.read_exact(buf);
+
+    let mut timeout = 1_000;
+    let (mut buf, serial) = loop {
+        if t.is_done() {
+            break t.wait();
+        }
+
+        timeout -= 1;
+        if timeout == 0 {
+            // give up and get the buffer back
+            break t.abort();
+        }
+    };
+
+    // the DMA is no longer writing to `buf` so we can reuse it
+    buf.iter_mut().for_each(|byte| *byte = 0);
+    let t = serial.read_exact(buf);
+
+    // ..
+
+    mem::drop(t);
+}
+
+fn frame(serial: Serial1) {
+    let buf = Pin::new(Box::new([0; 16]));
+
+    let t = serial.read_exact(buf);
+
+    // .. wait for the idle line interrupt; no more data is coming in ..
+
+    let n = t.bytes_transferred();
+    let (buf, _serial) = t.abort();
+
+    // only the first `n` bytes contain valid data
+    let _frame = &buf[..n];
+}
+
+fn main() {
+    let dma = Peripherals::take().unwrap().DMA1_CHANNEL1;
+    let serial = Serial1 { dma };
+
+    let t = serial.read_exact(Pin::new(Box::new([0xff; 16])));
+
+    // the mock DMA channel has no units left to transfer
+    assert_eq!(t.bytes_transferred(), 16);
+
+    // `abort` hands back the very same buffer, and the serial port
+    let (buf, serial) = t.abort();
+    assert_eq!(*buf, [0xff; 16]);
+
+    frame(serial);
+}
+
+// UNCHANGED
+
+/// A singleton that represents serial port #1
+pub struct Serial1 {
+    dma: Dma1Channel1,
+    // ..
+}
+
+impl<B> Transfer<B> {
+    /// Returns `true` if the DMA transfer has finished
+    pub fn is_done(&self) -> bool {
+        !Dma1Channel1::in_progress()
+    }
+}
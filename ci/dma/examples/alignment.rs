@@ -0,0 +1,60 @@
+//! Transfer width and address alignment
+
+#![deny(missing_docs, warnings)]
+
+use std::panic;
+
+use shared::{
+    registers::dma1_channel1::{CR, SAR},
+    Address, Dma1Channel1, MemoryAddress, Peripherals, TransferWidth, USART1_TX,
+};
+
+/// Sends out the given `buffer` in 16-bit units
+///
+/// NOTE a `[u16]` is always 2-byte aligned so this passes the alignment checks of
+/// `set_source_address`; a `&[u8]` cast to a pointer may not
+pub fn write_halfwords(dma: &mut Dma1Channel1, buffer: &'static [u16]) {
+    let width = TransferWidth::HalfWord;
+    let address = MemoryAddress::from_ptr(buffer.as_ptr());
+
+    debug_assert_eq!(address.get() % Dma1Channel1::required_alignment(width), 0);
+
+    dma.set_transfer_width(width);
+    dma.set_destination_address(USART1_TX, false);
+    dma.set_source_address(address, true);
+    // NOTE the length is in units of the transfer width, not in bytes
+    dma.set_transfer_length(buffer.len());
+    dma.start();
+}
+
+fn main() {
+    // units of data must be naturally aligned
+    assert_eq!(Dma1Channel1::required_alignment(TransferWidth::Byte), 1);
+    assert_eq!(Dma1Channel1::required_alignment(TransferWidth::HalfWord), 2);
+    assert_eq!(Dma1Channel1::required_alignment(TransferWidth::Word), 4);
+
+    static BUFFER: [u16; 4] = [0; 4];
+    let mut dma = Peripherals::take().unwrap().DMA1_CHANNEL1;
+
+    // the width is written to the PSIZE (bits 8 and 9) and MSIZE (bits 10 and 11) fields
+    write_halfwords(&mut dma, &BUFFER);
+    assert_eq!(CR.read() & 0b1111 << 8, 0b0101 << 8);
+    assert_eq!(dma.transfer_width(), TransferWidth::HalfWord);
+
+    // a `[u16]` passes the alignment checks of a half-word transfer
+    assert_eq!(SAR.read(), BUFFER.as_ptr() as usize);
+
+    // .. but the same memory, offset by one byte, is rejected in debug builds
+    let odd = MemoryAddress::from_ptr((BUFFER.as_ptr() as *const u8).wrapping_add(1));
+    panic::set_hook(Box::new(|_| {}));
+    let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        dma.set_source_address(odd, true);
+    }));
+    let _ = panic::take_hook();
+    assert_eq!(res.is_err(), cfg!(debug_assertions));
+
+    // a byte transfer accepts any address
+    dma.set_transfer_width(TransferWidth::Byte);
+    dma.set_source_address(odd, true);
+    assert_eq!(SAR.read(), odd.get());
+}
@@ -0,0 +1,179 @@
+//! Multiple DMA channels
+
+#![deny(missing_docs, warnings)]
+
+use core::{
+    hint,
+    marker::Unpin,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    ptr,
+    sync::atomic::{self, Ordering},
+};
+
+use as_slice::{AsMutSlice, AsSlice};
+use shared::{
+    Dma1Channel1, Dma1Channel2, DmaChannel, MemoryAddress, Peripherals, USART1_RX, USART1_TX,
+};
+
+/// A DMA transfer
+pub struct Transfer<B, C>
+where
+    C: DmaChannel,
+{
+    // NOTE: always `Some` variant
+    inner: Option<Inner<B, C>>,
+}
+
+// NOTE: previously named `Transfer<B>`
+struct Inner<B, C> {
+    buffer: Pin<B>,
+    serial: Serial1<C>,
+}
+
+impl<B, C> Transfer<B, C>
+where
+    C: DmaChannel,
+{
+    /// Blocks until the transfer is done and returns the buffer
+    pub fn wait(mut self) -> (Pin<B>, Serial1<C>) {
+        while !self.is_done() {}
+
+        atomic::compiler_fence(Ordering::Acquire);
+
+        let inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+        (inner.buffer, inner.serial)
+    }
+}
+
+impl<B, C> Drop for Transfer<B, C>
+where
+    C: DmaChannel,
+{
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.as_mut() {
+            // NOTE: this is a volatile write
+            inner.serial.dma.stop();
+
+            // we need a read here to make the Acquire fence effective
+            // we do *not* need this if `dma.stop` does a RMW operation
+            unsafe {
+                ptr::read_volatile(&0);
+            }
+
+            // we need a fence here for the same reason we need one in `Transfer.wait`
+            atomic::compiler_fence(Ordering::Acquire);
+        }
+    }
+}
+
+impl<C> Serial1<C>
+where
+    C: DmaChannel,
+{
+    /// Receives data into the given `buffer` until it's filled
+    ///
+    /// Returns a value that represents the in-progress DMA transfer
+    pub fn read_exact<B>(mut self, mut buffer: Pin<B>) -> Transfer<B, C>
+    where
+        B: DerefMut + 'static,
+        B::Target: AsMutSlice<Element = u8> + Unpin,
+    {
+        // .. same as before ..
+        let slice = buffer.as_mut_slice();
+        let (ptr, len) = (slice.as_mut_ptr(), slice.len());
+
+        self.dma.set_source_address(USART1_RX, false);
+        self.dma
+            .set_destination_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner {
+                buffer,
+                serial: self,
+            }),
+        }
+    }
+
+    /// Sends out the given `buffer`
+    ///
+    /// Returns a value that represents the in-progress DMA transfer
+    pub fn write_all<B>(mut self, buffer: Pin<B>) -> Transfer<B, C>
+    where
+        B: Deref + 'static,
+        B::Target: AsSlice<Element = u8>,
+    {
+        // .. same as before ..
+        let slice = buffer.as_slice();
+        let (ptr, len) = (slice.as_ptr(), slice.len());
+
+        self.dma.set_destination_address(USART1_TX, false);
+        self.dma
+            .set_source_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner {
+                buffer,
+                serial: self,
+            }),
+        }
+    }
+}
+
+fn echo(rx: Serial1<Dma1Channel1>, tx: Serial1<Dma1Channel2>) {
+    let buf = Pin::new(Box::new([0; 16]));
+
+    // this configures the registers of channel 1
+    let (buf, _rx) = rx.read_exact(buf).wait();
+
+    // this configures the registers of channel 2
+    let (_buf, _tx) = tx.write_all(buf).wait();
+}
+
+fn main() {
+    let p = Peripherals::take().unwrap();
+    let rx = Serial1 {
+        dma: p.DMA1_CHANNEL1,
+    };
+    let tx = Serial1 {
+        dma: p.DMA1_CHANNEL2,
+    };
+
+    // a buffer can go from a transfer on one channel to a transfer on the other one
+    let (buf, rx) = rx.read_exact(Pin::new(Box::new([1; 4]))).wait();
+    let (buf, tx) = tx.write_all(buf).wait();
+    assert_eq!(*buf, [1; 4]);
+
+    echo(rx, tx);
+}
+
+// UNCHANGED
+
+/// A singleton that represents serial port #1
+///
+/// `C` is the DMA channel used for transfers
+pub struct Serial1<C> {
+    dma: C,
+    // ..
+}
+
+impl<B, C> Transfer<B, C>
+where
+    C: DmaChannel,
+{
+    /// Returns `true` if the DMA transfer has finished
+    pub fn is_done(&self) -> bool {
+        !C::in_progress()
+    }
+}
@@ -0,0 +1,180 @@
+//! Circular transfers
+
+#![deny(missing_docs, warnings)]
+
+use core::{
+    hint,
+    marker::Unpin,
+    ops::DerefMut,
+    pin::Pin,
+    ptr,
+    sync::atomic::{self, Ordering},
+};
+
+use as_slice::AsMutSlice;
+use shared::{Dma1Channel1, MemoryAddress, Peripherals, USART1_RX};
+
+/// A DMA transfer that continuously fills a buffer, wrapping around at the end of it
+pub struct CircularTransfer<B> {
+    // NOTE: always `Some` variant
+    inner: Option<Inner<B>>,
+}
+
+struct Inner<B> {
+    buffer: Pin<B>,
+    serial: Serial1,
+}
+
+impl Serial1 {
+    /// Continuously receives data into the given `buffer`
+    ///
+    /// The DMA fills the buffer and then starts over from the beginning, forever. The received data
+    /// is read out one half of the buffer at a time, while the DMA writes to the other half; see
+    /// `CircularTransfer::read`
+    pub fn read_circular<B>(mut self, mut buffer: Pin<B>) -> CircularTransfer<B>
+    where
+        B: DerefMut + 'static,
+        B::Target: AsMutSlice<Element = u8> + Unpin,
+    {
+        let slice = buffer.as_mut_slice();
+        let (ptr, len) = (slice.as_mut_ptr(), slice.len());
+
+        // the buffer is split in two halves
+        assert!(len % 2 == 0);
+
+        self.dma.set_source_address(USART1_RX, false);
+        self.dma
+            .set_destination_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+        self.dma.set_circular(true);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        CircularTransfer {
+            inner: Some(Inner {
+                buffer,
+                serial: self,
+            }),
+        }
+    }
+}
+
+impl<B> CircularTransfer<B>
+where
+    B: DerefMut + 'static,
+    B::Target: AsMutSlice<Element = u8> + Unpin,
+{
+    /// Blocks until one half of the buffer has been filled and returns it
+    ///
+    /// The returned half is the one the DMA is *not* writing to: the first half after the
+    /// half-transfer event, the second half after the transfer-complete event. It must be processed
+    /// before the DMA wraps around and overwrites it, i.e. before the other half is filled.
+    pub fn read(&mut self) -> &[u8] {
+        let inner = self
+            .inner
+            .as_mut()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+
+        let first = loop {
+            let dma = &mut inner.serial.dma;
+
+            if dma.is_half_transfer_complete() {
+                // the DMA is now writing to the second half
+                dma.clear_half_transfer();
+                break true;
+            }
+
+            if dma.is_transfer_complete() {
+                // the DMA wrapped around and is now writing to the first half
+                dma.clear_transfer_complete();
+                break false;
+            }
+        };
+
+        // the DMA wrote the half we are about to read
+        atomic::compiler_fence(Ordering::Acquire);
+
+        ready_half(inner.buffer.as_mut_slice(), first)
+    }
+
+    /// Stops the transfer and returns the buffer
+    pub fn stop(mut self) -> (Pin<B>, Serial1) {
+        let mut inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+
+        inner.serial.dma.stop();
+        inner.serial.dma.set_circular(false);
+
+        atomic::compiler_fence(Ordering::Acquire);
+
+        (inner.buffer, inner.serial)
+    }
+}
+
+/// Returns the `first` (or second) half of the circular `buffer`
+fn ready_half(buffer: &[u8], first: bool) -> &[u8] {
+    let half = buffer.len() / 2;
+    if first {
+        &buffer[..half]
+    } else {
+        &buffer[half..]
+    }
+}
+
+// NOTE a circular transfer never finishes so it must be stopped before the buffer is freed
+impl<B> Drop for CircularTransfer<B> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.as_mut() {
+            // NOTE: this is a volatile write
+            inner.serial.dma.stop();
+
+            // we need a read here to make the Acquire fence effective
+            unsafe {
+                ptr::read_volatile(&0);
+            }
+
+            atomic::compiler_fence(Ordering::Acquire);
+        }
+    }
+}
+
+#[allow(dead_code)]
+fn stream(serial: Serial1) {
+    static mut BUF: [u8; 64] = [0; 64];
+
+    let buf = Pin::new(unsafe { &mut *ptr::addr_of_mut!(BUF) });
+
+    let mut t = serial.read_circular(buf);
+
+    // after the half-transfer event this returns `BUF[..32]`
+    let first = t.read();
+    let _ = first;
+
+    // after the transfer-complete event this returns `BUF[32..]`
+    let second = t.read();
+    let _ = second;
+
+    let (_buf, _serial) = t.stop();
+}
+
+fn main() {
+    // the half-transfer event hands out the first half, the transfer-complete event the second one
+    let buffer = [0, 1, 2, 3, 4, 5, 6, 7];
+    assert_eq!(ready_half(&buffer, true), [0, 1, 2, 3]);
+    assert_eq!(ready_half(&buffer, false), [4, 5, 6, 7]);
+
+    // stopping the transfer hands back the buffer and the serial port
+    let dma = Peripherals::take().unwrap().DMA1_CHANNEL1;
+    let t = Serial1 { dma }.read_circular(Pin::new(Box::new(buffer)));
+    let (buffer, _serial) = t.stop();
+    assert_eq!(*buffer, [0, 1, 2, 3, 4, 5, 6, 7]);
+}
+
+/// A singleton that represents serial port #1
+pub struct Serial1 {
+    dma: Dma1Channel1,
+    // ..
+}
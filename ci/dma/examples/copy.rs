@@ -0,0 +1,117 @@
+//! Memory to memory transfers
+
+#![deny(missing_docs, warnings)]
+
+use core::{
+    hint,
+    marker::Unpin,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    ptr,
+    sync::atomic::{self, Ordering},
+};
+
+use as_slice::{AsMutSlice, AsSlice};
+use shared::{Dma1Channel1, Peripherals};
+
+/// A memory to memory DMA transfer
+pub struct Transfer2<S, D> {
+    // NOTE: always `Some` variant
+    inner: Option<Inner<S, D>>,
+}
+
+struct Inner<S, D> {
+    src: Pin<S>,
+    dst: Pin<D>,
+    dma: Dma1Channel1,
+}
+
+/// Copies the contents of `src` into `dst` using the DMA
+///
+/// Returns a value that represents the in-progress DMA transfer
+///
+/// # Panics
+///
+/// This function panics if `src` and `dst` have different lengths
+pub fn copy<S, D>(mut dma: Dma1Channel1, src: Pin<S>, mut dst: Pin<D>) -> Transfer2<S, D>
+where
+    S: Deref + 'static,
+    S::Target: AsSlice<Element = u8>,
+    D: DerefMut + 'static,
+    D::Target: AsMutSlice<Element = u8> + Unpin,
+{
+    let src_slice = src.as_slice();
+    let dst_slice = dst.as_mut_slice();
+    assert_eq!(src_slice.len(), dst_slice.len());
+
+    dma.mem_to_mem(src_slice.as_ptr(), dst_slice.as_mut_ptr(), src_slice.len());
+
+    atomic::compiler_fence(Ordering::Release);
+    dma.start();
+
+    Transfer2 {
+        inner: Some(Inner { src, dst, dma }),
+    }
+}
+
+impl<S, D> Transfer2<S, D> {
+    /// Returns `true` if the DMA transfer has finished
+    pub fn is_done(&self) -> bool {
+        !Dma1Channel1::in_progress()
+    }
+
+    /// Blocks until the transfer is done and returns both buffers
+    pub fn wait(mut self) -> (Pin<S>, Pin<D>, Dma1Channel1) {
+        while !self.is_done() {}
+
+        atomic::compiler_fence(Ordering::Acquire);
+
+        let inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+        (inner.src, inner.dst, inner.dma)
+    }
+}
+
+impl<S, D> Drop for Transfer2<S, D> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.as_mut() {
+            // NOTE: this is a volatile write
+            inner.dma.stop();
+
+            // we need a read here to make the Acquire fence effective
+            unsafe {
+                ptr::read_volatile(&0);
+            }
+
+            atomic::compiler_fence(Ordering::Acquire);
+        }
+    }
+}
+
+fn example(dma: Dma1Channel1) {
+    static SRC: [u8; 16] = [0xAA; 16];
+
+    let dst = Pin::new(Box::new([0; 16]));
+
+    let t = copy(dma, Pin::new(&SRC), dst);
+
+    let (src, dst, _dma) = t.wait();
+
+    // the destination now holds a copy of the source
+    let _equal = src[..] == dst[..];
+}
+
+fn main() {
+    static SRC: [u8; 4] = [1, 2, 3, 4];
+
+    let dma = Peripherals::take().unwrap().DMA1_CHANNEL1;
+
+    // `wait` hands back both buffers and the channel; the mock DMA channel doesn't move any data
+    let t = copy(dma, Pin::new(&SRC), Pin::new(Box::new([0; 4])));
+    let (src, dst, dma) = t.wait();
+    assert_eq!((*src, *dst), ([1, 2, 3, 4], [0; 4]));
+
+    example(dma);
+}
@@ -0,0 +1,170 @@
+//! Double buffering
+
+#![deny(missing_docs, warnings)]
+
+use core::{
+    hint,
+    marker::Unpin,
+    ops::DerefMut,
+    ptr,
+    sync::atomic::{self, Ordering},
+};
+
+use as_slice::AsMutSlice;
+use shared::{Dma1Channel1, MemoryAddress, Peripherals, USART1_RX};
+
+/// A DMA transfer that alternately fills two buffers
+///
+/// While the DMA fills one buffer the other one, the one that was last filled, can be read out.
+pub struct DoubleBuffer<B> {
+    // NOTE: always `Some` variant
+    inner: Option<Inner<B>>,
+}
+
+struct Inner<B> {
+    buffers: [B; 2],
+    serial: Serial1,
+}
+
+impl Serial1 {
+    /// Continuously receives data, alternating between the two `buffers`
+    pub fn read_double<B>(mut self, mut buffers: [B; 2]) -> DoubleBuffer<B>
+    where
+        B: DerefMut + 'static,
+        B::Target: AsMutSlice<Element = u8> + Unpin,
+    {
+        let len = buffers[0].as_mut_slice().len();
+        // the length register applies to both buffers
+        assert_eq!(len, buffers[1].as_mut_slice().len());
+
+        let ptr0 = buffers[0].as_mut_slice().as_mut_ptr();
+        let ptr1 = buffers[1].as_mut_slice().as_mut_ptr();
+
+        self.dma.set_source_address(USART1_RX, false);
+        self.dma
+            .set_destination_address(MemoryAddress::from_ptr(ptr0), true);
+        self.dma.set_memory1_address(MemoryAddress::from_ptr(ptr1));
+        self.dma.set_transfer_length(len);
+        self.dma.set_double_buffer(true);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        DoubleBuffer {
+            inner: Some(Inner {
+                buffers,
+                serial: self,
+            }),
+        }
+    }
+}
+
+impl<B> DoubleBuffer<B>
+where
+    B: DerefMut + 'static,
+    B::Target: AsMutSlice<Element = u8> + Unpin,
+{
+    /// Returns the buffer that was just filled, if the DMA finished filling one since the last call
+    ///
+    /// The DMA has already switched to the other buffer by the time this returns so the returned
+    /// buffer can be freely read (and modified). NOTE the returned buffer must be processed before
+    /// the DMA fills the other buffer; at that point the DMA switches back and starts overwriting it.
+    pub fn next_filled(&mut self) -> Option<&mut B> {
+        let inner = self
+            .inner
+            .as_mut()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+
+        if !inner.serial.dma.is_transfer_complete() {
+            return None;
+        }
+
+        inner.serial.dma.clear_transfer_complete();
+
+        // NOTE the hardware switches the target buffer when the transfer completes, *before*
+        // raising the flag, so by now the DMA is writing to `current_target`; the other buffer is
+        // the one that was just filled
+        let filled = filled_buffer(inner.serial.dma.current_target());
+
+        // the DMA writes to `filled` happen before the CPU reads (prevents the compiler from moving
+        // the reads of the buffer to before the flag check)
+        atomic::compiler_fence(Ordering::Acquire);
+
+        Some(&mut inner.buffers[filled])
+    }
+
+    /// Stops the transfer and returns the buffers
+    pub fn stop(mut self) -> ([B; 2], Serial1) {
+        let mut inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+
+        inner.serial.dma.stop();
+        inner.serial.dma.set_double_buffer(false);
+
+        atomic::compiler_fence(Ordering::Acquire);
+
+        (inner.buffers, inner.serial)
+    }
+}
+
+/// Returns the index of the buffer that was just filled given the `current_target` of the DMA
+fn filled_buffer(current_target: usize) -> usize {
+    1 - current_target
+}
+
+// NOTE the transfer never finishes so it must be stopped before the buffers are freed
+impl<B> Drop for DoubleBuffer<B> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.as_mut() {
+            // NOTE: this is a volatile write
+            inner.serial.dma.stop();
+
+            // we need a read here to make the Acquire fence effective
+            unsafe {
+                ptr::read_volatile(&0);
+            }
+
+            atomic::compiler_fence(Ordering::Acquire);
+        }
+    }
+}
+
+#[allow(dead_code)]
+fn ping_pong(serial: Serial1) {
+    let mut t = serial.read_double([Box::new([0; 32]), Box::new([0; 32])]);
+
+    // first transfer-complete event: the DMA switched to buffer 1; this returns buffer 0
+    while t.next_filled().is_none() {}
+
+    // second transfer-complete event: the DMA switched back to buffer 0; this returns buffer 1
+    while t.next_filled().is_none() {}
+
+    let (_buffers, _serial) = t.stop();
+}
+
+fn main() {
+    // the buffer that was just filled is the one the DMA is *not* writing to
+    assert_eq!(filled_buffer(0), 1);
+    assert_eq!(filled_buffer(1), 0);
+
+    let dma = Peripherals::take().unwrap().DMA1_CHANNEL1;
+    let mut t = Serial1 { dma }.read_double([Box::new([0; 4]), Box::new([1; 4])]);
+
+    // the simulated transfer completes as soon as it starts, while the DMA writes to buffer 0
+    assert_eq!(t.next_filled().map(|buffer| **buffer), Some([1; 4]));
+
+    // the transfer complete flag has been cleared so the same buffer is not handed out twice
+    assert!(t.next_filled().is_none());
+
+    // stopping the transfer hands back both buffers, in order
+    let ([first, second], _serial) = t.stop();
+    assert_eq!((*first, *second), ([0; 4], [1; 4]));
+}
+
+/// A singleton that represents serial port #1
+pub struct Serial1 {
+    dma: Dma1Channel1,
+    // ..
+}
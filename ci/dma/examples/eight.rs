@@ -13,7 +13,7 @@ use core::{
 };
 
 use as_slice::{AsMutSlice, AsSlice};
-use shared::{Dma1Channel1, USART1_RX, USART1_TX};
+use shared::{Dma1Channel1, MemoryAddress, USART1_RX, USART1_TX};
 
 /// A DMA transfer
 pub struct Transfer<B> {
@@ -74,7 +74,8 @@ impl Serial1 {
         let (ptr, len) = (slice.as_mut_ptr(), slice.len());
 
         self.dma.set_source_address(USART1_RX, false);
-        self.dma.set_destination_address(ptr as usize, true);
+        let address = MemoryAddress::from_ptr(ptr);
+        self.dma.set_destination_address(address, true);
         self.dma.set_transfer_length(len);
 
         atomic::compiler_fence(Ordering::Release);
@@ -101,7 +102,8 @@ impl Serial1 {
         let (ptr, len) = (slice.as_ptr(), slice.len());
 
         self.dma.set_destination_address(USART1_TX, false);
-        self.dma.set_source_address(ptr as usize, true);
+        let address = MemoryAddress::from_ptr(ptr);
+        self.dma.set_source_address(address, true);
         self.dma.set_transfer_length(len);
 
         atomic::compiler_fence(Ordering::Release);
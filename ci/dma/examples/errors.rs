@@ -0,0 +1,230 @@
+//! Error reporting
+
+#![deny(missing_docs, warnings)]
+
+use core::{
+    hint,
+    marker::Unpin,
+    mem,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    ptr,
+    sync::atomic::{self, Ordering},
+};
+
+use as_slice::{AsMutSlice, AsSlice};
+use shared::{
+    registers::dma1_channel1::{CR, ISR},
+    Dma1Channel1, DmaError, MemoryAddress, Peripherals, USART1_RX, USART1_TX,
+};
+
+/// A DMA transfer
+pub struct Transfer<B> {
+    // NOTE: always `Some` variant
+    inner: Option<Inner<B>>,
+}
+
+// NOTE: previously named `Transfer<B>`
+struct Inner<B> {
+    buffer: Pin<B>,
+    serial: Serial1,
+}
+
+/// The outcome of a DMA transfer
+///
+/// Both variants hand back the buffer and the serial port
+pub type TransferResult<B> = Result<(Pin<B>, Serial1), (DmaError, Pin<B>, Serial1)>;
+
+impl<B> Transfer<B> {
+    /// Blocks until the transfer is done and returns the buffer
+    ///
+    /// If the transfer failed the error is returned along with the buffer. NOTE the contents of
+    /// the buffer are unspecified in that case
+    pub fn wait(mut self) -> TransferResult<B> {
+        // NOTE the channel is disabled on a transfer error so this loop also ends in that case
+        while !self.is_done() {}
+
+        atomic::compiler_fence(Ordering::Acquire);
+
+        let mut inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+
+        if inner.serial.dma.error() {
+            inner.serial.dma.clear_error();
+
+            Err((DmaError::Transfer, inner.buffer, inner.serial))
+        } else {
+            Ok((inner.buffer, inner.serial))
+        }
+    }
+
+    /// Stops the transfer and returns the buffer
+    ///
+    /// Use this to give up on a transfer that's taking too long. NOTE some of the data may have
+    /// been transferred
+    pub fn abort(mut self) -> (Pin<B>, Serial1) {
+        // NOTE taking `inner` out disarms the destructor so the DMA is only stopped once
+        let mut inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+
+        // NOTE: this is a volatile write
+        inner.serial.dma.stop();
+
+        // we need a read here to make the Acquire fence effective
+        unsafe {
+            ptr::read_volatile(&0);
+        }
+
+        // same reason as in `Transfer.wait`
+        atomic::compiler_fence(Ordering::Acquire);
+
+        (inner.buffer, inner.serial)
+    }
+}
+
+impl<B> Drop for Transfer<B> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.as_mut() {
+            // NOTE: this is a volatile write
+            inner.serial.dma.stop();
+
+            // we need a read here to make the Acquire fence effective
+            // we do *not* need this if `dma.stop` does a RMW operation
+            unsafe {
+                ptr::read_volatile(&0);
+            }
+
+            // we need a fence here for the same reason we need one in `Transfer.wait`
+            atomic::compiler_fence(Ordering::Acquire);
+        }
+    }
+}
+
+impl Serial1 {
+    /// Receives data into the given `buffer` until it's filled
+    ///
+    /// Returns a value that represents the in-progress DMA transfer
+    pub fn read_exact<B>(mut self, mut buffer: Pin<B>) -> Transfer<B>
+    where
+        B: DerefMut + 'static,
+        B::Target: AsMutSlice<Element = u8> + Unpin,
+    {
+        // .. same as before ..
+        let slice = buffer.as_mut_slice();
+        let (ptr, len) = (slice.as_mut_ptr(), slice.len());
+
+        self.dma.set_source_address(USART1_RX, false);
+        self.dma
+            .set_destination_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner {
+                buffer,
+                serial: self,
+            }),
+        }
+    }
+
+    /// Sends out the given `buffer`
+    ///
+    /// Returns a value that represents the in-progress DMA transfer
+    pub fn write_all<B>(mut self, buffer: Pin<B>) -> Transfer<B>
+    where
+        B: Deref + 'static,
+        B::Target: AsSlice<Element = u8>,
+    {
+        // .. same as before ..
+        let slice = buffer.as_slice();
+        let (ptr, len) = (slice.as_ptr(), slice.len());
+
+        self.dma.set_destination_address(USART1_TX, false);
+        self.dma
+            .set_source_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner {
+                buffer,
+                serial: self,
+            }),
+        }
+    }
+}
+
+fn retry(serial: Serial1) {
+    let buf = Pin::new(Box::new([0; 16]));
+
+    let mut t = serial.read_exact(buf);
+
+    let (buf, _serial) = loop {
+        match t.wait() {
+            Ok((buf, serial)) => break (buf, serial),
+            Err((_error, buf, serial)) => {
+                // the buffer is recovered even if the transfer failed; try again
+                t = serial.read_exact(buf);
+            }
+        }
+    };
+
+    // .. do stuff with `buf` ..
+    mem::drop(buf);
+}
+
+fn main() {
+    let dma = Peripherals::take().unwrap().DMA1_CHANNEL1;
+    let serial = Serial1 { dma };
+
+    // a bus error: the hardware raises the transfer error flag (TEIF, bit 3) and disables the
+    // channel (EN, bit 0)
+    let t = serial.read_exact(Pin::new(Box::new([0xff; 16])));
+    ISR.modify(|isr| isr | 1 << 3);
+    CR.modify(|cr| cr & !1);
+
+    let (buf, serial) = match t.wait() {
+        Err((DmaError::Transfer, buf, serial)) => (buf, serial),
+        Ok(_) => panic!("the transfer error was not reported"),
+    };
+
+    // the buffer is handed back, and nothing wrote to it in this simulation
+    assert_eq!(*buf, [0xff; 16]);
+    // the error flag has been cleared
+    assert_eq!(ISR.read() & 1 << 3, 0);
+
+    // without an error the same buffer can be used for a new transfer
+    let (buf, serial) = match serial.read_exact(buf).wait() {
+        Ok(done) => done,
+        Err((error, ..)) => panic!("unexpected {:?}", error),
+    };
+    assert_eq!(*buf, [0xff; 16]);
+
+    // `retry` starts over after an error
+    ISR.modify(|isr| isr | 1 << 3);
+    retry(serial);
+    assert_eq!(ISR.read() & 1 << 3, 0);
+}
+
+// UNCHANGED
+
+/// A singleton that represents serial port #1
+pub struct Serial1 {
+    dma: Dma1Channel1,
+    // ..
+}
+
+impl<B> Transfer<B> {
+    /// Returns `true` if the DMA transfer has finished
+    pub fn is_done(&self) -> bool {
+        !Dma1Channel1::in_progress()
+    }
+}
@@ -0,0 +1,51 @@
+//! Memset via DMA
+
+#![deny(missing_docs, warnings)]
+
+use std::{panic, ptr};
+
+use shared::{
+    registers::dma1_channel1::{CR, DAR, NDTR, SAR},
+    Dma1Channel1, Peripherals,
+};
+
+/// Zeroes the given `region` using the DMA
+pub fn clear(dma: &mut Dma1Channel1, region: &'static mut [u8; 256]) -> &'static mut [u8] {
+    // `region` is owned by the `Fill` until `wait` hands it back
+    dma.fill(region, 0).wait()
+}
+
+fn main() {
+    static mut REGION: [u8; 256] = [0xff; 256];
+
+    let mut dma = Peripherals::take().unwrap().DMA1_CHANNEL1;
+    let region = unsafe { &mut *ptr::addr_of_mut!(REGION) };
+    let start = region.as_ptr() as usize;
+
+    // the region is handed back once the fill is done
+    let region = clear(&mut dma, region);
+    assert_eq!(region.as_ptr() as usize, start);
+
+    // the source is the byte that holds the fill value; its address is not incremented (bit 6)
+    assert_eq!(unsafe { *(SAR.read() as *const u8) }, 0);
+    assert_eq!(CR.read() & (1 << 6), 0);
+
+    // the destination is the region; its address is incremented after every byte (bit 7)
+    assert_eq!(DAR.read(), start);
+    assert_eq!(CR.read() & (1 << 7), 1 << 7);
+
+    // one unit per byte of the region
+    assert_eq!(NDTR.read(), 256);
+
+    // the channel was enabled (bit 0)
+    assert_eq!(CR.read() & 1, 1);
+
+    // the transfer count register is only 16 bits wide; a longer transfer is rejected
+    panic::set_hook(Box::new(|_| {}));
+    let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        dma.set_transfer_length(0x1_0000);
+    }));
+    let _ = panic::take_hook();
+    assert!(res.is_err());
+    assert_eq!(NDTR.read(), 256);
+}
@@ -4,7 +4,7 @@
 
 use core::sync::atomic::{self, Ordering};
 
-use shared::{Dma1Channel1, USART1_RX, USART1_TX};
+use shared::{Dma1Channel1, MemoryAddress, USART1_RX, USART1_TX};
 
 // as-slice = "0.1.0"
 use as_slice::{AsMutSlice, AsSlice};
@@ -24,7 +24,8 @@ impl Serial1 {
         self.dma.set_source_address(USART1_RX, false);
 
         // NOTE: tweaked
-        self.dma.set_destination_address(ptr as usize, true);
+        let address = MemoryAddress::from_ptr(ptr);
+        self.dma.set_destination_address(address, true);
         self.dma.set_transfer_length(len);
 
         atomic::compiler_fence(Ordering::Release);
@@ -50,7 +51,8 @@ impl Serial1 {
         self.dma.set_destination_address(USART1_TX, false);
 
         // NOTE: tweaked
-        self.dma.set_source_address(ptr as usize, true);
+        let address = MemoryAddress::from_ptr(ptr);
+        self.dma.set_source_address(address, true);
         self.dma.set_transfer_length(len);
 
         atomic::compiler_fence(Ordering::Release);
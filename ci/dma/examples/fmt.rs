@@ -0,0 +1,33 @@
+//! Formatted output
+
+#![deny(missing_docs, warnings)]
+
+use core::fmt::{self, Write};
+
+use shared::{
+    registers::serial1::{CR3, DR},
+    Peripherals, Serial1,
+};
+
+/// Reports a measurement over the serial port
+pub fn report(serial: &mut Serial1, x: u32) -> fmt::Result {
+    writeln!(serial, "x = {}", x)
+}
+
+fn main() {
+    let mut serial = Peripherals::take().unwrap().SERIAL1;
+
+    // the bytes are sent out one by one; the last one is left in the data register
+    report(&mut serial, 42).unwrap();
+    assert_eq!(DR.read(), usize::from(b'\n'));
+
+    write!(serial, "{:#x}", 255).unwrap();
+    assert_eq!(DR.read(), usize::from(b'f'));
+
+    // a serial error aborts the formatting: here the transmitter is in use by a DMA transfer
+    // (DMAT, bit 7)
+    CR3.write(1 << 7);
+    DR.write(0);
+    assert!(write!(serial, "{:#x}", 255).is_err());
+    assert_eq!(DR.read(), 0);
+}
@@ -4,7 +4,7 @@
 
 use core::sync::atomic::{self, Ordering};
 
-use shared::{Dma1Channel1, USART1_RX, USART1_TX};
+use shared::{Dma1Channel1, MemoryAddress, USART1_RX, USART1_TX};
 
 impl Serial1 {
     /// Receives data into the given `buffer` until it's filled
@@ -12,8 +12,8 @@ impl Serial1 {
     /// Returns a value that represents the in-progress DMA transfer
     pub fn read_exact(mut self, buffer: &'static mut [u8]) -> Transfer<&'static mut [u8]> {
         self.dma.set_source_address(USART1_RX, false);
-        self.dma
-            .set_destination_address(buffer.as_mut_ptr() as usize, true);
+        let address = MemoryAddress::from_ptr(buffer.as_mut_ptr());
+        self.dma.set_destination_address(address, true);
         self.dma.set_transfer_length(buffer.len());
 
         // NOTE: added
@@ -33,7 +33,8 @@ impl Serial1 {
     /// Returns a value that represents the in-progress DMA transfer
     pub fn write_all(mut self, buffer: &'static [u8]) -> Transfer<&'static [u8]> {
         self.dma.set_destination_address(USART1_TX, false);
-        self.dma.set_source_address(buffer.as_ptr() as usize, true);
+        let address = MemoryAddress::from_ptr(buffer.as_ptr());
+        self.dma.set_source_address(address, true);
         self.dma.set_transfer_length(buffer.len());
 
         // NOTE: added
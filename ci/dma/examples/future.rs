@@ -0,0 +1,326 @@
+//! `async` transfers
+
+#![deny(missing_docs, warnings)]
+
+use core::{
+    cell::UnsafeCell,
+    future::Future,
+    hint,
+    marker::Unpin,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    ptr,
+    sync::atomic::{self, AtomicBool, AtomicUsize, Ordering},
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+
+use as_slice::{AsMutSlice, AsSlice};
+use shared::{
+    registers::dma1_channel1::ISR, Dma1Channel1, MemoryAddress, Peripherals, USART1_RX, USART1_TX,
+};
+
+/// A DMA transfer
+pub struct Transfer<B> {
+    // NOTE: always `Some` variant
+    inner: Option<Inner<B>>,
+}
+
+// NOTE: previously named `Transfer<B>`
+struct Inner<B> {
+    buffer: Pin<B>,
+    serial: Serial1,
+}
+
+impl<B> Transfer<B> {
+    /// Blocks until the transfer is done and returns the buffer
+    pub fn wait(mut self) -> (Pin<B>, Serial1) {
+        while !self.is_done() {}
+
+        atomic::compiler_fence(Ordering::Acquire);
+
+        let inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+        (inner.buffer, inner.serial)
+    }
+}
+
+// NOTE `Transfer` never hands out pinned references to its fields (`buffer` is already pinned) so
+// it doesn't need to stay put once pinned
+impl<B> Unpin for Transfer<B> {}
+
+impl<B> Future for Transfer<B> {
+    type Output = (Pin<B>, Serial1);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        // NOTE register the waker *before* checking the state of the transfer; otherwise the
+        // transfer could complete (and the interrupt fire) between the check and the registration
+        // and the task would never be woken up
+        WAKER.register(cx.waker());
+
+        if !self.is_done() {
+            return Poll::Pending;
+        }
+
+        atomic::compiler_fence(Ordering::Acquire);
+
+        let inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+        Poll::Ready((inner.buffer, inner.serial))
+    }
+}
+
+/// Waker of the task that's awaiting the transfer on DMA1 channel 1
+static WAKER: WakerCell = WakerCell::new();
+
+/// A `Waker` slot shared between a task and an interrupt handler
+struct WakerCell {
+    locked: AtomicBool,
+    waker: UnsafeCell<Option<Waker>>,
+}
+
+// NOTE(unsafe) `waker` is only accessed while `locked` is held
+unsafe impl Sync for WakerCell {}
+
+impl WakerCell {
+    const fn new() -> Self {
+        WakerCell {
+            locked: AtomicBool::new(false),
+            waker: UnsafeCell::new(None),
+        }
+    }
+
+    /// Stores `waker`, replacing the previous one
+    fn register(&self, waker: &Waker) {
+        // NOTE the interrupt handler never holds the lock for long and tasks don't preempt each
+        // other so this spins at most a few iterations
+        while self.locked.swap(true, Ordering::Acquire) {}
+
+        let slot = unsafe { &mut *self.waker.get() };
+        match slot {
+            Some(old) if old.will_wake(waker) => {}
+            _ => *slot = Some(waker.clone()),
+        }
+
+        self.locked.store(false, Ordering::Release);
+    }
+
+    /// Wakes the registered task, if any
+    fn wake(&self) {
+        // the task was preempted while registering its waker; it will check the state of the
+        // transfer right after registering so there's no need to wake it up
+        if self.locked.swap(true, Ordering::Acquire) {
+            return;
+        }
+
+        let waker = unsafe { (*self.waker.get()).take() };
+
+        self.locked.store(false, Ordering::Release);
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Transfer complete interrupt
+#[allow(non_snake_case)]
+fn DMA1_CHANNEL1() {
+    // .. clear the transfer complete flag ..
+
+    WAKER.wake();
+}
+
+impl<B> Drop for Transfer<B> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.as_mut() {
+            // NOTE: this is a volatile write
+            inner.serial.dma.stop();
+
+            // we need a read here to make the Acquire fence effective
+            // we do *not* need this if `dma.stop` does a RMW operation
+            unsafe {
+                ptr::read_volatile(&0);
+            }
+
+            // we need a fence here for the same reason we need one in `Transfer.wait`
+            atomic::compiler_fence(Ordering::Acquire);
+        }
+    }
+}
+
+impl Serial1 {
+    /// Receives data into the given `buffer` until it's filled
+    ///
+    /// Returns a value that represents the in-progress DMA transfer
+    pub fn read_exact<B>(mut self, mut buffer: Pin<B>) -> Transfer<B>
+    where
+        B: DerefMut + 'static,
+        B::Target: AsMutSlice<Element = u8> + Unpin,
+    {
+        // .. same as before ..
+        let slice = buffer.as_mut_slice();
+        let (ptr, len) = (slice.as_mut_ptr(), slice.len());
+
+        self.dma.set_source_address(USART1_RX, false);
+        self.dma
+            .set_destination_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner {
+                buffer,
+                serial: self,
+            }),
+        }
+    }
+
+    /// Receives data into the given `buffer` until it's filled
+    ///
+    /// Returns a future that resolves when the transfer is done. NOTE the transfer complete
+    /// interrupt must be enabled
+    pub fn read_exact_async<B>(
+        self,
+        buffer: Pin<B>,
+    ) -> impl Future<Output = (Pin<B>, Serial1)> + Unpin
+    where
+        B: DerefMut + 'static,
+        B::Target: AsMutSlice<Element = u8> + Unpin,
+    {
+        self.read_exact(buffer)
+    }
+
+    /// Sends out the given `buffer`
+    ///
+    /// Returns a value that represents the in-progress DMA transfer
+    pub fn write_all<B>(mut self, buffer: Pin<B>) -> Transfer<B>
+    where
+        B: Deref + 'static,
+        B::Target: AsSlice<Element = u8>,
+    {
+        // .. same as before ..
+        let slice = buffer.as_slice();
+        let (ptr, len) = (slice.as_ptr(), slice.len());
+
+        self.dma.set_destination_address(USART1_TX, false);
+        self.dma
+            .set_source_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner {
+                buffer,
+                serial: self,
+            }),
+        }
+    }
+}
+
+fn block_on<F>(mut future: F) -> F::Output
+where
+    F: Future + Unpin,
+{
+    // a waker that does nothing; this executor keeps polling the future until it completes
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        fn noop(_: *const ()) {}
+
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+        RawWaker::new(ptr::null(), &VTABLE)
+    }
+
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        if let Poll::Ready(output) = Pin::new(&mut future).poll(&mut cx) {
+            return output;
+        }
+
+        // .. a real executor would sleep (`WFI`) here until the interrupt handler wakes the task ..
+    }
+}
+
+fn example(serial: Serial1) {
+    let buf = Pin::new(Box::new([0; 16]));
+
+    let (buf, _serial) = block_on(serial.read_exact_async(buf));
+
+    // .. do stuff with `buf` ..
+    drop(buf);
+}
+
+/// Returns a waker that increments `wakes` each time it's woken up
+fn counting_waker(wakes: &'static AtomicUsize) -> Waker {
+    fn clone(data: *const ()) -> RawWaker {
+        RawWaker::new(data, &VTABLE)
+    }
+    fn wake(data: *const ()) {
+        unsafe { &*(data as *const AtomicUsize) }.fetch_add(1, Ordering::Relaxed);
+    }
+    fn noop(_: *const ()) {}
+
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake, noop);
+
+    unsafe { Waker::from_raw(RawWaker::new(wakes as *const _ as *const (), &VTABLE)) }
+}
+
+fn main() {
+    static WAKES: AtomicUsize = AtomicUsize::new(0);
+
+    let dma = Peripherals::take().unwrap().DMA1_CHANNEL1;
+    let mut future = Serial1 { dma }.read_exact_async(Pin::new(Box::new([0xff; 16])));
+
+    // the simulated transfer is done as soon as it starts; clear its transfer complete flag (TCIF,
+    // bit 1) so that it's still in progress when the future is first polled
+    ISR.modify(|isr| isr & !(1 << 1));
+
+    let waker = counting_waker(&WAKES);
+    let mut cx = Context::from_waker(&waker);
+    assert!(Pin::new(&mut future).poll(&mut cx).is_pending());
+    assert_eq!(WAKES.load(Ordering::Relaxed), 0);
+
+    // the transfer completes and the interrupt handler wakes up the task
+    ISR.modify(|isr| isr | 1 << 1);
+    DMA1_CHANNEL1();
+    assert_eq!(WAKES.load(Ordering::Relaxed), 1);
+
+    // the woken task gets the buffer back
+    let serial = match Pin::new(&mut future).poll(&mut cx) {
+        Poll::Ready((buf, serial)) => {
+            assert_eq!(*buf, [0xff; 16]);
+            serial
+        }
+        Poll::Pending => panic!("the transfer is done"),
+    };
+
+    // the executor polls the future to completion
+    example(serial);
+}
+
+// UNCHANGED
+
+/// A singleton that represents serial port #1
+pub struct Serial1 {
+    dma: Dma1Channel1,
+    // ..
+}
+
+impl<B> Transfer<B> {
+    /// Returns `true` if the DMA transfer has finished
+    pub fn is_done(&self) -> bool {
+        !Dma1Channel1::in_progress()
+    }
+}
@@ -0,0 +1,155 @@
+//! Receiving variable length frames
+
+#![deny(missing_docs, warnings)]
+
+use core::{
+    hint,
+    marker::Unpin,
+    ops::DerefMut,
+    pin::Pin,
+    ptr,
+    sync::atomic::{self, Ordering},
+};
+
+use as_slice::AsMutSlice;
+use shared::{Dma1Channel1, MemoryAddress, USART1_RX};
+
+/// A DMA transfer
+pub struct Transfer<B> {
+    // NOTE: always `Some` variant
+    inner: Option<Inner<B>>,
+}
+
+struct Inner<B> {
+    buffer: Pin<B>,
+    // number of bytes requested when the transfer started
+    len: usize,
+    serial: Serial1,
+}
+
+impl<B> Transfer<B> {
+    /// Blocks until the buffer is full or the line goes idle
+    ///
+    /// Returns the number of bytes received, which are at the start of the returned buffer
+    pub fn wait_idle(mut self) -> (usize, Pin<B>, Serial1) {
+        loop {
+            let inner = self
+                .inner
+                .as_ref()
+                .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+
+            if !Dma1Channel1::in_progress() || inner.serial.usart.is_idle() {
+                break;
+            }
+        }
+
+        // NOTE taking `inner` out disarms the destructor so the DMA is only stopped once
+        let mut inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+
+        // NOTE: this is a volatile write
+        inner.serial.dma.stop();
+
+        // we need a read here to make the Acquire fence effective
+        unsafe {
+            ptr::read_volatile(&0);
+        }
+
+        // the DMA is done writing to the buffer
+        atomic::compiler_fence(Ordering::Acquire);
+
+        let filled_len = filled_len(inner.len, inner.serial.dma.remaining());
+
+        inner.serial.usart.clear_idle();
+        inner.serial.usart.disable_idle_interrupt();
+
+        (filled_len, inner.buffer, inner.serial)
+    }
+}
+
+/// Number of bytes received by a transfer of `len` bytes that still had `remaining` bytes left
+/// (the count-down register) when it was stopped
+fn filled_len(len: usize, remaining: usize) -> usize {
+    len - remaining
+}
+
+impl<B> Drop for Transfer<B> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.as_mut() {
+            // NOTE: this is a volatile write
+            inner.serial.dma.stop();
+
+            // we need a read here to make the Acquire fence effective
+            unsafe {
+                ptr::read_volatile(&0);
+            }
+
+            atomic::compiler_fence(Ordering::Acquire);
+        }
+    }
+}
+
+impl Serial1 {
+    /// Receives data into the given `buffer` until it's filled or the line goes idle
+    ///
+    /// Use this to receive frames of unknown length; see `Transfer::wait_idle`
+    pub fn read_until_idle<B>(mut self, mut buffer: Pin<B>) -> Transfer<B>
+    where
+        B: DerefMut + 'static,
+        B::Target: AsMutSlice<Element = u8> + Unpin,
+    {
+        let slice = buffer.as_mut_slice();
+        let (ptr, len) = (slice.as_mut_ptr(), slice.len());
+
+        self.dma.set_source_address(USART1_RX, false);
+        self.dma
+            .set_destination_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+
+        // a stale idle flag would end the transfer right away
+        self.usart.clear_idle();
+        self.usart.enable_idle_interrupt();
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner {
+                buffer,
+                len,
+                serial: self,
+            }),
+        }
+    }
+}
+
+#[allow(dead_code)]
+fn frames(serial: Serial1) {
+    let buf = Pin::new(Box::new([0; 16]));
+
+    let t = serial.read_until_idle(buf);
+
+    // .. the sender goes quiet after a few bytes ..
+
+    let (n, buf, _serial) = t.wait_idle();
+
+    // only the first `n` bytes contain valid data
+    let _frame = &buf[..n];
+}
+
+fn main() {
+    // the line goes idle after receiving 7 of the 16 requested bytes: the DMA has 9 bytes left
+    assert_eq!(filled_len(16, 9), 7);
+
+    // the buffer filled up before the line went idle
+    assert_eq!(filled_len(16, 0), 16);
+}
+
+/// A singleton that represents serial port #1
+pub struct Serial1 {
+    dma: Dma1Channel1,
+    usart: shared::Serial1,
+    // ..
+}
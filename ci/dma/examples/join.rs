@@ -0,0 +1,298 @@
+//! Waiting on two DMA transfers at once
+
+#![deny(missing_docs, warnings)]
+
+use core::{
+    cell::Cell,
+    hint,
+    marker::Unpin,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    ptr,
+    sync::atomic::{self, Ordering},
+};
+
+use as_slice::{AsMutSlice, AsSlice};
+use shared::{Dma1Channel1, Dma1Channel2, DmaChannel, MemoryAddress, USART1_RX, USART1_TX};
+
+/// A DMA transfer
+pub struct Transfer<B, C>
+where
+    C: DmaChannel,
+{
+    // NOTE: always `Some` variant
+    inner: Option<Inner<B, C>>,
+}
+
+struct Inner<B, C> {
+    buffer: Pin<B>,
+    serial: Serial1<C>,
+}
+
+impl<B, C> Transfer<B, C>
+where
+    C: DmaChannel,
+{
+    /// Blocks until the transfer is done and returns the buffer
+    pub fn wait(self) -> (Pin<B>, Serial1<C>) {
+        while !self.is_done() {}
+
+        atomic::compiler_fence(Ordering::Acquire);
+
+        self.into_parts()
+    }
+
+    // NOTE the caller must have observed the end of the transfer *and* inserted the `Acquire`
+    // fence
+    fn into_parts(mut self) -> (Pin<B>, Serial1<C>) {
+        let inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+        (inner.buffer, inner.serial)
+    }
+}
+
+/// Blocks until *both* transfers are done
+///
+/// Use this for full-duplex communication: e.g. send out a buffer while receiving into another
+pub fn join<B1, C1, B2, C2>(
+    t1: Transfer<B1, C1>,
+    t2: Transfer<B2, C2>,
+) -> (Pin<B1>, Pin<B2>, Serial1<C1>, Serial1<C2>)
+where
+    C1: DmaChannel,
+    C2: DmaChannel,
+{
+    poll_both(|| t1.is_done(), || t2.is_done());
+
+    // a single fence covers both buffers: it must come after *both* transfers have finished
+    atomic::compiler_fence(Ordering::Acquire);
+
+    let (buf1, serial1) = t1.into_parts();
+    let (buf2, serial2) = t2.into_parts();
+    (buf1, buf2, serial1, serial2)
+}
+
+/// The outcome of `select`: the parts of the transfer that finished first and the transfer that's
+/// still in progress
+pub enum Select<B1, C1, B2, C2>
+where
+    C1: DmaChannel,
+    C2: DmaChannel,
+{
+    /// The first transfer finished first
+    First(Pin<B1>, Serial1<C1>, Transfer<B2, C2>),
+    /// The second transfer finished first
+    Second(Transfer<B1, C1>, Pin<B2>, Serial1<C2>),
+}
+
+/// Blocks until *either* transfer is done
+pub fn select<B1, C1, B2, C2>(t1: Transfer<B1, C1>, t2: Transfer<B2, C2>) -> Select<B1, C1, B2, C2>
+where
+    C1: DmaChannel,
+    C2: DmaChannel,
+{
+    let which = poll_either(|| t1.is_done(), || t2.is_done());
+
+    // the DMA is done with *one* of the buffers; the other one is still in use
+    atomic::compiler_fence(Ordering::Acquire);
+
+    match which {
+        Which::First => {
+            let (buf, serial) = t1.into_parts();
+            Select::First(buf, serial, t2)
+        }
+        Which::Second => {
+            let (buf, serial) = t2.into_parts();
+            Select::Second(t1, buf, serial)
+        }
+    }
+}
+
+/// Polls both transfers until both are done
+///
+/// A transfer that's done is not polled again
+fn poll_both(mut done1: impl FnMut() -> bool, mut done2: impl FnMut() -> bool) {
+    let (mut is_done1, mut is_done2) = (false, false);
+
+    while !(is_done1 && is_done2) {
+        is_done1 = is_done1 || done1();
+        is_done2 = is_done2 || done2();
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Which {
+    First,
+    Second,
+}
+
+/// Polls both transfers until one of them is done
+///
+/// If both finish at the same time the first one is reported
+fn poll_either(mut done1: impl FnMut() -> bool, mut done2: impl FnMut() -> bool) -> Which {
+    loop {
+        if done1() {
+            return Which::First;
+        }
+
+        if done2() {
+            return Which::Second;
+        }
+    }
+}
+
+impl<B, C> Drop for Transfer<B, C>
+where
+    C: DmaChannel,
+{
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.as_mut() {
+            // NOTE: this is a volatile write
+            inner.serial.dma.stop();
+
+            // we need a read here to make the Acquire fence effective
+            // we do *not* need this if `dma.stop` does a RMW operation
+            unsafe {
+                ptr::read_volatile(&0);
+            }
+
+            // we need a fence here for the same reason we need one in `Transfer.wait`
+            atomic::compiler_fence(Ordering::Acquire);
+        }
+    }
+}
+
+impl<C> Serial1<C>
+where
+    C: DmaChannel,
+{
+    /// Receives data into the given `buffer` until it's filled
+    ///
+    /// Returns a value that represents the in-progress DMA transfer
+    pub fn read_exact<B>(mut self, mut buffer: Pin<B>) -> Transfer<B, C>
+    where
+        B: DerefMut + 'static,
+        B::Target: AsMutSlice<Element = u8> + Unpin,
+    {
+        // .. same as before ..
+        let slice = buffer.as_mut_slice();
+        let (ptr, len) = (slice.as_mut_ptr(), slice.len());
+
+        self.dma.set_source_address(USART1_RX, false);
+        self.dma
+            .set_destination_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner {
+                buffer,
+                serial: self,
+            }),
+        }
+    }
+
+    /// Sends out the given `buffer`
+    ///
+    /// Returns a value that represents the in-progress DMA transfer
+    pub fn write_all<B>(mut self, buffer: Pin<B>) -> Transfer<B, C>
+    where
+        B: Deref + 'static,
+        B::Target: AsSlice<Element = u8>,
+    {
+        // .. same as before ..
+        let slice = buffer.as_slice();
+        let (ptr, len) = (slice.as_ptr(), slice.len());
+
+        self.dma.set_destination_address(USART1_TX, false);
+        self.dma
+            .set_source_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner {
+                buffer,
+                serial: self,
+            }),
+        }
+    }
+}
+
+#[allow(dead_code)]
+fn full_duplex(rx: Serial1<Dma1Channel1>, tx: Serial1<Dma1Channel2>) {
+    let rx_buf = Pin::new(Box::new([0; 16]));
+    let tx_buf = Pin::new(Box::new([0; 16]));
+
+    let rt = rx.read_exact(rx_buf);
+    let tt = tx.write_all(tx_buf);
+
+    let (_rx_buf, _tx_buf, _rx, _tx) = join(rt, tt);
+}
+
+/// A mock DMA channel that reports the end of its transfer on the `done_at`-th poll
+struct Mock {
+    done_at: usize,
+    polls: Cell<usize>,
+}
+
+impl Mock {
+    fn new(done_at: usize) -> Self {
+        Mock {
+            done_at,
+            polls: Cell::new(0),
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.polls.set(self.polls.get() + 1);
+        self.polls.get() >= self.done_at
+    }
+}
+
+fn main() {
+    // `join` returns only after both transfers are done
+    let (fast, slow) = (Mock::new(3), Mock::new(7));
+    poll_both(|| fast.is_done(), || slow.is_done());
+    assert_eq!(fast.polls.get(), 3);
+    assert_eq!(slow.polls.get(), 7);
+
+    // same thing if the first transfer is the slow one
+    let (slow, fast) = (Mock::new(7), Mock::new(3));
+    poll_both(|| slow.is_done(), || fast.is_done());
+    assert_eq!(slow.polls.get(), 7);
+    assert_eq!(fast.polls.get(), 3);
+
+    // `select` returns as soon as one of the transfers is done
+    let (slow, fast) = (Mock::new(7), Mock::new(3));
+    assert_eq!(
+        poll_either(|| slow.is_done(), || fast.is_done()),
+        Which::Second
+    );
+    assert_eq!(slow.polls.get(), 3);
+    assert_eq!(fast.polls.get(), 3);
+}
+
+/// A singleton that represents serial port #1
+///
+/// `C` is the DMA channel used for transfers
+pub struct Serial1<C> {
+    dma: C,
+    // ..
+}
+
+impl<B, C> Transfer<B, C>
+where
+    C: DmaChannel,
+{
+    /// Returns `true` if the DMA transfer has finished
+    pub fn is_done(&self) -> bool {
+        !C::in_progress()
+    }
+}
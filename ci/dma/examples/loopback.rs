@@ -0,0 +1,73 @@
+//! Loopback self-test
+
+#![deny(missing_docs, warnings)]
+
+use shared::{
+    registers::serial1::{CR1, SR},
+    Error, Peripherals, Serial1,
+};
+
+/// Sends out the `pattern` in loopback mode and checks that the same bytes are received back
+pub fn self_test(serial: &mut Serial1, pattern: &[u8]) -> Result<bool, Error> {
+    serial.set_loopback(true);
+
+    let passed = check_echo(pattern, |byte| {
+        serial.write(byte)?;
+        serial.read()
+    });
+
+    // leave loopback mode even if the test was cut short by an error
+    serial.set_loopback(false);
+
+    passed
+}
+
+/// Sends every byte of `pattern` through `echo` and checks that it comes back unchanged
+fn check_echo(
+    pattern: &[u8],
+    mut echo: impl FnMut(u8) -> Result<u8, Error>,
+) -> Result<bool, Error> {
+    let mut passed = true;
+    for &byte in pattern {
+        if echo(byte)? != byte {
+            passed = false;
+        }
+    }
+
+    Ok(passed)
+}
+
+fn main() {
+    let pattern = [0x00, 0x55, 0xaa, 0xff];
+
+    // a working loopback receives back every byte sent out
+    assert_eq!(check_echo(&pattern, Ok), Ok(true));
+
+    // a stuck data line corrupts some of the bytes
+    assert_eq!(check_echo(&pattern, |byte| Ok(byte & 0x7f)), Ok(false));
+
+    // reception errors are reported as is
+    assert_eq!(
+        check_echo(&pattern, |_| Err(Error::Framing)),
+        Err(Error::Framing)
+    );
+
+    let mut serial = Peripherals::take().unwrap().SERIAL1;
+
+    // the loopback bit (LBE, bit 14) of the control register 1 is set and cleared; the other bits
+    // are left alone
+    CR1.write(1 << 3);
+    serial.set_loopback(true);
+    assert_eq!(CR1.read(), 1 << 14 | 1 << 3);
+    serial.set_loopback(false);
+    assert_eq!(CR1.read(), 1 << 3);
+
+    // the simulated data register hands back the last byte written to it, like loopback mode does
+    SR.write(1 << 5);
+    assert_eq!(self_test(&mut serial, &pattern), Ok(true));
+
+    // the self-test leaves loopback mode, even when a reception error cuts it short (FE, bit 1)
+    SR.write(1 << 5 | 1 << 1);
+    assert_eq!(self_test(&mut serial, &pattern), Err(Error::Framing));
+    assert_eq!(CR1.read() & (1 << 14), 0);
+}
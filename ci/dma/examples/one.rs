@@ -2,7 +2,7 @@
 
 #![deny(missing_docs, warnings)]
 
-use shared::{Dma1Channel1, USART1_RX, USART1_TX};
+use shared::{Dma1Channel1, MemoryAddress, USART1_RX, USART1_TX};
 
 /// A singleton that represents serial port #1
 pub struct Serial1 {
@@ -17,7 +17,8 @@ impl Serial1 {
     /// Returns a value that represents the in-progress DMA transfer
     pub fn write_all<'a>(mut self, buffer: &'a [u8]) -> Transfer<&'a [u8]> {
         self.dma.set_destination_address(USART1_TX, false);
-        self.dma.set_source_address(buffer.as_ptr() as usize, true);
+        let address = MemoryAddress::from_ptr(buffer.as_ptr());
+        self.dma.set_source_address(address, true);
         self.dma.set_transfer_length(buffer.len());
 
         self.dma.start();
@@ -52,8 +53,8 @@ impl Serial1 {
     /// Returns a value that represents the in-progress DMA transfer
     pub fn read_exact<'a>(&mut self, buffer: &'a mut [u8]) -> Transfer<&'a mut [u8]> {
         self.dma.set_source_address(USART1_RX, false);
-        self.dma
-            .set_destination_address(buffer.as_mut_ptr() as usize, true);
+        let address = MemoryAddress::from_ptr(buffer.as_mut_ptr());
+        self.dma.set_destination_address(address, true);
         self.dma.set_transfer_length(buffer.len());
 
         self.dma.start();
@@ -0,0 +1,26 @@
+//! Channel priority levels
+
+#![deny(missing_docs, warnings)]
+
+use shared::{registers::dma1_channel1::CR, Dma1Channel1, Peripherals, Priority};
+
+/// Makes `dma` win the arbitration against the other channels
+pub fn low_latency(dma: &mut Dma1Channel1) {
+    dma.set_priority(Priority::VeryHigh);
+}
+
+fn main() {
+    let mut dma = Peripherals::take().unwrap().DMA1_CHANNEL1;
+
+    // the PL bits are bits 12 and 13
+    low_latency(&mut dma);
+    assert_eq!(CR.read(), 0b11 << 12);
+
+    // starting the transfer preserves the priority level
+    dma.start();
+    assert_eq!(CR.read(), 0b11 << 12 | 1);
+
+    // lowering the priority level clears the PL bits but leaves the other bits alone
+    dma.set_priority(Priority::Medium);
+    assert_eq!(CR.read(), 0b01 << 12 | 1);
+}
@@ -0,0 +1,202 @@
+//! Moving transfers across contexts
+
+#![deny(missing_docs, warnings)]
+
+use core::{
+    hint,
+    marker::{PhantomData, Unpin},
+    mem,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    ptr,
+    sync::atomic::{self, Ordering},
+};
+
+use as_slice::{AsMutSlice, AsSlice};
+use shared::{Dma1Channel1, MemoryAddress, Peripherals, USART1_RX, USART1_TX};
+
+/// A DMA transfer
+pub struct Transfer<B> {
+    // NOTE: always `Some` variant
+    inner: Option<Inner<B>>,
+    // opts out of `Sync` (and `Send`; see below)
+    _not_sync: PhantomData<*const ()>,
+}
+
+// NOTE(unsafe) a `Transfer` can be moved to a different execution context (e.g. started in `main`
+// and completed in an interrupt handler) because the buffer is owned (`B: 'static`) and the DMA
+// channel is accessed through the `Serial1` singleton, which the `Transfer` owns. This requires `B`
+// to be `Send`: e.g. a `Transfer<Rc<_>>` must not be moved to another context as a clone of the
+// `Rc` may be left behind.
+//
+// `Transfer` is *not* `Sync`: `Transfer` and `Serial1` have no synchronization of their own so a
+// shared reference to a `Transfer` must not be used from two contexts at the same time
+unsafe impl<B> Send for Transfer<B> where B: Send {}
+
+// NOTE: previously named `Transfer<B>`
+struct Inner<B> {
+    buffer: Pin<B>,
+    serial: Serial1,
+}
+
+impl<B> Transfer<B> {
+    /// Blocks until the transfer is done and returns the buffer
+    pub fn wait(mut self) -> (Pin<B>, Serial1) {
+        while !self.is_done() {}
+
+        atomic::compiler_fence(Ordering::Acquire);
+
+        let inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+        (inner.buffer, inner.serial)
+    }
+
+    /// Leaves the transfer running in the background and returns the serial port
+    ///
+    /// The buffer is leaked: it's never dropped nor handed back, so the DMA can keep accessing it
+    /// for as long as it wants. This is sound because the buffer is owned (`B: 'static`); if `B` is
+    /// a reference to a `static` buffer, the `static` stays borrowed forever.
+    ///
+    /// NOTE starting a new transfer on the returned `Serial1` will reconfigure the DMA channel,
+    /// interrupting the leaked transfer
+    pub fn leak(mut self) -> Serial1 {
+        let inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+
+        // NOTE this doesn't run the destructor of the buffer
+        mem::forget(inner.buffer);
+
+        inner.serial
+    }
+}
+
+impl<B> Drop for Transfer<B> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.as_mut() {
+            // NOTE: this is a volatile write
+            inner.serial.dma.stop();
+
+            // we need a read here to make the Acquire fence effective
+            // we do *not* need this if `dma.stop` does a RMW operation
+            unsafe {
+                ptr::read_volatile(&0);
+            }
+
+            // we need a fence here for the same reason we need one in `Transfer.wait`
+            atomic::compiler_fence(Ordering::Acquire);
+        }
+    }
+}
+
+impl Serial1 {
+    /// Receives data into the given `buffer` until it's filled
+    ///
+    /// Returns a value that represents the in-progress DMA transfer
+    pub fn read_exact<B>(mut self, mut buffer: Pin<B>) -> Transfer<B>
+    where
+        B: DerefMut + 'static,
+        B::Target: AsMutSlice<Element = u8> + Unpin,
+    {
+        // .. same as before ..
+        let slice = buffer.as_mut_slice();
+        let (ptr, len) = (slice.as_mut_ptr(), slice.len());
+
+        self.dma.set_source_address(USART1_RX, false);
+        self.dma
+            .set_destination_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner {
+                buffer,
+                serial: self,
+            }),
+            _not_sync: PhantomData,
+        }
+    }
+
+    /// Sends out the given `buffer`
+    ///
+    /// Returns a value that represents the in-progress DMA transfer
+    pub fn write_all<B>(mut self, buffer: Pin<B>) -> Transfer<B>
+    where
+        B: Deref + 'static,
+        B::Target: AsSlice<Element = u8>,
+    {
+        // .. same as before ..
+        let slice = buffer.as_slice();
+        let (ptr, len) = (slice.as_ptr(), slice.len());
+
+        self.dma.set_destination_address(USART1_TX, false);
+        self.dma
+            .set_source_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner {
+                buffer,
+                serial: self,
+            }),
+            _not_sync: PhantomData,
+        }
+    }
+}
+
+fn assert_send<T>()
+where
+    T: Send,
+{
+}
+
+fn send() {
+    assert_send::<Transfer<&'static mut [u8; 16]>>();
+    assert_send::<Transfer<Box<[u8; 16]>>>();
+
+    // NOTE `ui/src/bin/rc-transfer-not-send.rs` checks that a `Transfer<Rc<_>>` is not `Send`
+}
+
+fn fire_and_forget(serial: Serial1) {
+    let buf = Pin::new(Box::new(*b"Hello, world!\n"));
+
+    // we don't care about getting the buffer back
+    let _serial = serial.write_all(buf).leak();
+}
+
+fn main() {
+    send();
+
+    let dma = Peripherals::take().unwrap().DMA1_CHANNEL1;
+    let serial = Serial1 { dma };
+
+    // start a transfer in this thread and complete it in a different one
+    let t = serial.write_all(Pin::new(Box::new(*b"Hello")));
+    let (buf, serial) = std::thread::spawn(move || t.wait()).join().unwrap();
+    assert_eq!(*buf, *b"Hello");
+
+    fire_and_forget(serial);
+}
+
+// UNCHANGED
+
+/// A singleton that represents serial port #1
+pub struct Serial1 {
+    dma: Dma1Channel1,
+    // ..
+}
+
+impl<B> Transfer<B> {
+    /// Returns `true` if the DMA transfer has finished
+    pub fn is_done(&self) -> bool {
+        !Dma1Channel1::in_progress()
+    }
+}
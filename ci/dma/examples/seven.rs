@@ -10,7 +10,7 @@ use core::{
 };
 
 use as_slice::{AsMutSlice, AsSlice};
-use shared::{Dma1Channel1, USART1_RX, USART1_TX};
+use shared::{Dma1Channel1, MemoryAddress, USART1_RX, USART1_TX};
 
 impl Serial1 {
     /// Receives data into the given `buffer` until it's filled
@@ -27,7 +27,8 @@ impl Serial1 {
         let (ptr, len) = (slice.as_mut_ptr(), slice.len());
 
         self.dma.set_source_address(USART1_RX, false);
-        self.dma.set_destination_address(ptr as usize, true);
+        self.dma
+            .set_destination_address(MemoryAddress::from_ptr(ptr), true);
         self.dma.set_transfer_length(len);
 
         atomic::compiler_fence(Ordering::Release);
@@ -53,7 +54,8 @@ impl Serial1 {
         let (ptr, len) = (slice.as_ptr(), slice.len());
 
         self.dma.set_destination_address(USART1_TX, false);
-        self.dma.set_source_address(ptr as usize, true);
+        self.dma
+            .set_source_address(MemoryAddress::from_ptr(ptr), true);
         self.dma.set_transfer_length(len);
 
         atomic::compiler_fence(Ordering::Release);
@@ -0,0 +1,262 @@
+//! Splitting the serial port into independent transmitter and receiver halves
+
+#![deny(missing_docs, warnings)]
+
+use core::{
+    fmt::{self, Write},
+    hint,
+    marker::Unpin,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    ptr,
+    sync::atomic::{self, Ordering},
+};
+use std::thread;
+
+use as_slice::{AsMutSlice, AsSlice};
+use shared::{Dma1Channel1, Dma1Channel2, DmaChannel, Error, MemoryAddress, USART1_RX, USART1_TX};
+
+/// A half of `Serial1`
+pub trait Half {
+    /// The DMA channel this half uses for transfers
+    type Dma: DmaChannel;
+
+    /// Returns the DMA channel of this half
+    fn dma(&mut self) -> &mut Self::Dma;
+}
+
+/// The transmitter half of `Serial1`
+///
+/// Uses DMA channel 2 for transfers
+pub struct Tx {
+    dma: Dma1Channel2,
+}
+
+/// The receiver half of `Serial1`
+///
+/// Uses DMA channel 1 for transfers
+pub struct Rx {
+    dma: Dma1Channel1,
+}
+
+impl Half for Tx {
+    type Dma = Dma1Channel2;
+
+    fn dma(&mut self) -> &mut Dma1Channel2 {
+        &mut self.dma
+    }
+}
+
+impl Half for Rx {
+    type Dma = Dma1Channel1;
+
+    fn dma(&mut self) -> &mut Dma1Channel1 {
+        &mut self.dma
+    }
+}
+
+impl Serial1 {
+    /// Splits the serial port into its transmitter and receiver halves
+    ///
+    /// The halves don't share any state so they can be moved into different tasks
+    pub fn split(self) -> (Tx, Rx) {
+        (Tx { dma: self.tx_dma }, Rx { dma: self.rx_dma })
+    }
+
+    /// Recombines the two halves returned by `split`
+    pub fn join(tx: Tx, rx: Rx) -> Serial1 {
+        Serial1 {
+            rx_dma: rx.dma,
+            tx_dma: tx.dma,
+        }
+    }
+}
+
+/// A DMA transfer
+pub struct Transfer<B, H>
+where
+    H: Half,
+{
+    // NOTE: always `Some` variant
+    inner: Option<Inner<B, H>>,
+}
+
+struct Inner<B, H> {
+    buffer: Pin<B>,
+    half: H,
+}
+
+impl<B, H> Transfer<B, H>
+where
+    H: Half,
+{
+    /// Returns `true` if the DMA transfer has finished
+    pub fn is_done(&self) -> bool {
+        !H::Dma::in_progress()
+    }
+
+    /// Blocks until the transfer is done and returns the buffer
+    pub fn wait(mut self) -> (Pin<B>, H) {
+        while !self.is_done() {}
+
+        atomic::compiler_fence(Ordering::Acquire);
+
+        let inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+        (inner.buffer, inner.half)
+    }
+}
+
+impl<B, H> Drop for Transfer<B, H>
+where
+    H: Half,
+{
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.as_mut() {
+            // NOTE: this is a volatile write
+            inner.half.dma().stop();
+
+            // we need a read here to make the Acquire fence effective
+            // we do *not* need this if `dma.stop` does a RMW operation
+            unsafe {
+                ptr::read_volatile(&0);
+            }
+
+            // we need a fence here for the same reason we need one in `Transfer.wait`
+            atomic::compiler_fence(Ordering::Acquire);
+        }
+    }
+}
+
+impl Rx {
+    /// Receives data into the given `buffer` until it's filled
+    ///
+    /// Returns a value that represents the in-progress DMA transfer
+    pub fn read_exact<B>(mut self, mut buffer: Pin<B>) -> Transfer<B, Rx>
+    where
+        B: DerefMut + 'static,
+        B::Target: AsMutSlice<Element = u8> + Unpin,
+    {
+        // .. same as before ..
+        let slice = buffer.as_mut_slice();
+        let (ptr, len) = (slice.as_mut_ptr(), slice.len());
+
+        self.dma.set_source_address(USART1_RX, false);
+        self.dma
+            .set_destination_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner { buffer, half: self }),
+        }
+    }
+}
+
+impl Tx {
+    /// Sends out the given `buffer`
+    ///
+    /// Returns a value that represents the in-progress DMA transfer
+    pub fn write_all<B>(mut self, buffer: Pin<B>) -> Transfer<B, Tx>
+    where
+        B: Deref + 'static,
+        B::Target: AsSlice<Element = u8>,
+    {
+        // .. same as before ..
+        let slice = buffer.as_slice();
+        let (ptr, len) = (slice.as_ptr(), slice.len());
+
+        self.dma.set_destination_address(USART1_TX, false);
+        self.dma
+            .set_source_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner { buffer, half: self }),
+        }
+    }
+}
+
+impl Tx {
+    /// Sends out a single byte
+    ///
+    /// NOTE: blocks if the output FIFO buffer is full
+    pub fn write(&mut self, byte: u8) -> Result<(), Error> {
+        shared::registers::serial1::DR.write(usize::from(byte));
+        Ok(())
+    }
+}
+
+/// Formatted output: `write!(tx, "x = {}", x)`
+impl fmt::Write for Tx {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            self.write(byte).map_err(|_| fmt::Error)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[allow(dead_code)]
+fn assert_send<T>()
+where
+    T: Send,
+{
+}
+
+#[allow(dead_code)]
+fn send() {
+    // each half can be moved into a different task
+    assert_send::<Tx>();
+    assert_send::<Rx>();
+}
+
+fn main() {
+    let p = shared::Peripherals::take().unwrap();
+
+    let serial = Serial1 {
+        rx_dma: p.DMA1_CHANNEL1,
+        tx_dma: p.DMA1_CHANNEL2,
+    };
+
+    let (tx, rx) = serial.split();
+
+    // the halves operate independently from each other
+    let sender = thread::spawn(move || {
+        let buf = Pin::new(Box::new(*b"Hello, world!\n"));
+        tx.write_all(buf).wait()
+    });
+    let receiver = thread::spawn(move || {
+        let buf = Pin::new(Box::new([0; 16]));
+        rx.read_exact(buf).wait()
+    });
+
+    let (tx_buf, mut tx) = sender.join().unwrap();
+    let (rx_buf, rx) = receiver.join().unwrap();
+
+    // both buffers are given back
+    assert_eq!(*tx_buf, *b"Hello, world!\n");
+    assert_eq!(rx_buf.len(), 16);
+
+    // formatted output goes out through the transmitter half; its last byte is left in the data
+    // register
+    write!(tx, "{:#x}", 255).unwrap();
+    assert_eq!(shared::registers::serial1::DR.read(), usize::from(b'f'));
+
+    let _serial = Serial1::join(tx, rx);
+}
+
+/// A singleton that represents serial port #1
+pub struct Serial1 {
+    rx_dma: Dma1Channel1,
+    tx_dma: Dma1Channel2,
+    // ..
+}
@@ -0,0 +1,48 @@
+//! Reception errors
+
+#![deny(missing_docs, warnings)]
+
+use shared::{
+    registers::serial1::{CR3, DR, SR},
+    Error, Peripherals, Serial1,
+};
+
+/// Receives a byte; bytes that arrive corrupted are skipped
+pub fn read_valid(serial: &mut Serial1) -> u8 {
+    loop {
+        match serial.read() {
+            Ok(byte) => return byte,
+            // the byte is garbage; wait for the next one
+            Err(Error::Framing) | Err(Error::Parity) | Err(Error::Noise) => {}
+            // some bytes were lost; they can't be recovered
+            Err(Error::Overrun) | Err(Error::Timeout) => {}
+        }
+    }
+}
+
+fn main() {
+    let mut serial = Peripherals::take().unwrap().SERIAL1;
+
+    // a byte was received (RXNE, bit 5) but its stop bit was not detected (FE, bit 1)
+    SR.write(1 << 5 | 1 << 1);
+    assert_eq!(serial.read(), Err(Error::Framing));
+
+    // the overrun error (ORE, bit 3) is reported first
+    SR.write(1 << 5 | 1 << 3 | 1 << 1);
+    assert_eq!(serial.read(), Err(Error::Overrun));
+
+    // no error
+    SR.write(1 << 5);
+    DR.write(0x42);
+    assert_eq!(serial.read(), Ok(0x42));
+    assert_eq!(read_valid(&mut serial), 0x42);
+
+    // a DMA transfer is using the transmitter (DMAT, bit 7)
+    CR3.write(1 << 7);
+    assert_eq!(serial.write(0x24), Err(Error::Overrun));
+    assert_eq!(DR.read(), 0x42);
+
+    CR3.write(0);
+    assert_eq!(serial.write(0x24), Ok(()));
+    assert_eq!(DR.read(), 0x24);
+}
@@ -0,0 +1,17 @@
+//! Singletons
+
+#![deny(missing_docs, warnings)]
+
+use shared::Peripherals;
+
+fn main() {
+    // the first call hands out the peripherals ..
+    {
+        let p = Peripherals::take();
+        assert!(p.is_some());
+    }
+
+    // .. all the following ones return `None`, even after the peripherals are dropped
+    assert!(Peripherals::take().is_none());
+    assert!(Peripherals::take().is_none());
+}
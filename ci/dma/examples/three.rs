@@ -2,7 +2,7 @@
 
 #![deny(missing_docs, warnings)]
 
-use shared::{Dma1Channel1, USART1_RX, USART1_TX};
+use shared::{Dma1Channel1, MemoryAddress, USART1_RX, USART1_TX};
 
 /// A DMA transfer
 pub struct Transfer<B> {
@@ -32,7 +32,7 @@ impl Serial1 {
     pub fn read_exact(mut self, buffer: &'static mut [u8]) -> Transfer<&'static mut [u8]> {
         self.dma.set_source_address(USART1_RX, false);
         self.dma
-            .set_destination_address(buffer.as_mut_ptr() as usize, true);
+            .set_destination_address(MemoryAddress::from_ptr(buffer.as_mut_ptr()), true);
         self.dma.set_transfer_length(buffer.len());
 
         self.dma.start();
@@ -52,7 +52,8 @@ impl Serial1 {
     // NOTE we now take `self` by value
     pub fn write_all(mut self, buffer: &'static [u8]) -> Transfer<&'static [u8]> {
         self.dma.set_destination_address(USART1_TX, false);
-        self.dma.set_source_address(buffer.as_ptr() as usize, true);
+        self.dma
+            .set_source_address(MemoryAddress::from_ptr(buffer.as_ptr()), true);
         self.dma.set_transfer_length(buffer.len());
 
         self.dma.start();
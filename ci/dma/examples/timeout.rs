@@ -0,0 +1,44 @@
+//! Bounded waits on the serial port
+
+#![deny(missing_docs, warnings)]
+
+use shared::{
+    registers::{
+        dwt::CYCCNT,
+        serial1::{DR, SR},
+    },
+    Error, Peripherals, Serial1,
+};
+
+/// Receives a `frame` of bytes; gives up if the sender goes quiet for more than `cycles`
+pub fn read_frame(serial: &mut Serial1, frame: &mut [u8], cycles: u32) -> Result<(), Error> {
+    for byte in frame {
+        *byte = serial.read_timeout(cycles)?;
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let mut serial = Peripherals::take().unwrap().SERIAL1;
+
+    // a byte is available (RXNE, bit 5)
+    SR.modify(|sr| sr | 1 << 5);
+    DR.write(0x42);
+
+    let mut frame = [0; 2];
+    assert_eq!(read_frame(&mut serial, &mut frame, 100), Ok(()));
+    assert_eq!(frame, [0x42; 2]);
+
+    // the serial port never receives another byte
+    SR.modify(|sr| sr & !(1 << 5));
+    assert_eq!(serial.read_timeout(100), Err(Error::Timeout));
+
+    // the cycle counter wraps around while waiting
+    CYCCNT.write(u32::MAX as usize - 15);
+    assert_eq!(serial.read_timeout(100), Err(Error::Timeout));
+
+    // the output FIFO never drains (TXE, bit 7, stays cleared)
+    SR.modify(|sr| sr & !(1 << 7));
+    assert_eq!(serial.write_timeout(0, 100), Err(Error::Timeout));
+}
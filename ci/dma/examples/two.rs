@@ -2,7 +2,7 @@
 
 #![deny(missing_docs, warnings)]
 
-use shared::{Dma1Channel1, USART1_RX, USART1_TX};
+use shared::{Dma1Channel1, MemoryAddress, USART1_RX, USART1_TX};
 
 impl Serial1 {
     /// Receives data into the given `buffer` until it's filled
@@ -12,7 +12,7 @@ impl Serial1 {
         // .. same as before ..
         self.dma.set_source_address(USART1_RX, false);
         self.dma
-            .set_destination_address(buffer.as_mut_ptr() as usize, true);
+            .set_destination_address(MemoryAddress::from_ptr(buffer.as_mut_ptr()), true);
         self.dma.set_transfer_length(buffer.len());
 
         self.dma.start();
@@ -26,7 +26,8 @@ impl Serial1 {
     pub fn write_all(mut self, buffer: &'static [u8]) -> Transfer<&'static [u8]> {
         // .. same as before ..
         self.dma.set_destination_address(USART1_TX, false);
-        self.dma.set_source_address(buffer.as_ptr() as usize, true);
+        self.dma
+            .set_source_address(MemoryAddress::from_ptr(buffer.as_ptr()), true);
         self.dma.set_transfer_length(buffer.len());
 
         self.dma.start();
@@ -0,0 +1,170 @@
+//! 16-bit and 32-bit transfers
+
+#![deny(missing_docs, warnings)]
+
+use core::{
+    hint,
+    marker::Unpin,
+    mem,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    ptr,
+    sync::atomic::{self, Ordering},
+};
+
+use as_slice::{AsMutSlice, AsSlice};
+use shared::{
+    registers::dma1_channel1::{CR, NDTR},
+    Dma1Channel1, MemoryAddress, Peripherals, Word, USART1_RX, USART1_TX,
+};
+
+/// A DMA transfer
+pub struct Transfer<B> {
+    // NOTE: always `Some` variant
+    inner: Option<Inner<B>>,
+}
+
+struct Inner<B> {
+    buffer: Pin<B>,
+    serial: Serial1,
+}
+
+impl Serial1 {
+    /// Receives data into the given `buffer` until it's filled
+    ///
+    /// Returns a value that represents the in-progress DMA transfer
+    pub fn read_exact<B, W>(mut self, mut buffer: Pin<B>) -> Transfer<B>
+    where
+        B: DerefMut + 'static,
+        B::Target: AsMutSlice<Element = W> + Unpin,
+        W: Word, // <- NEW!
+    {
+        let slice = buffer.as_mut_slice();
+        let (ptr, len) = (slice.as_mut_ptr(), slice.len());
+
+        // NOTE the width must be set first: the address setters check the alignment against it
+        self.dma.set_transfer_width(W::WIDTH); // <- NEW!
+        self.dma.set_source_address(USART1_RX, false);
+        self.dma
+            .set_destination_address(MemoryAddress::from_ptr(ptr), true);
+        // NOTE `len` is the number of `W` elements, not the number of bytes
+        self.dma.set_transfer_length(len);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner {
+                buffer,
+                serial: self,
+            }),
+        }
+    }
+
+    /// Sends out the given `buffer`
+    ///
+    /// Returns a value that represents the in-progress DMA transfer
+    pub fn write_all<B, W>(mut self, buffer: Pin<B>) -> Transfer<B>
+    where
+        B: Deref + 'static,
+        B::Target: AsSlice<Element = W>,
+        W: Word, // <- NEW!
+    {
+        let slice = buffer.as_slice();
+        let (ptr, len) = (slice.as_ptr(), slice.len());
+
+        self.dma.set_transfer_width(W::WIDTH); // <- NEW!
+        self.dma.set_destination_address(USART1_TX, false);
+        self.dma
+            .set_source_address(MemoryAddress::from_ptr(ptr), true);
+        self.dma.set_transfer_length(len);
+
+        atomic::compiler_fence(Ordering::Release);
+        self.dma.start();
+
+        Transfer {
+            inner: Some(Inner {
+                buffer,
+                serial: self,
+            }),
+        }
+    }
+}
+
+fn halfwords(serial: Serial1) {
+    static mut BUF: [u16; 8] = [0; 8];
+
+    let buf = Pin::new(unsafe { &mut *ptr::addr_of_mut!(BUF) });
+
+    // transfers 8 half-words: the transfer length is set to 8, not to 16
+    let (buf, serial) = serial.read_exact(buf).wait();
+
+    // ..
+
+    let _ = (buf, serial);
+}
+
+/// Returns `true` if the transfer width of `W` matches its size, which `Word` requires
+fn width_matches_size<W: Word>() -> bool {
+    Dma1Channel1::required_alignment(W::WIDTH) == mem::size_of::<W>()
+}
+
+fn main() {
+    assert!(width_matches_size::<u8>());
+    assert!(width_matches_size::<u16>());
+    assert!(width_matches_size::<u32>());
+
+    // a `[u16; 8]` buffer passes the alignment checks of a half-word transfer
+    let dma = Peripherals::take().unwrap().DMA1_CHANNEL1;
+    halfwords(Serial1 { dma });
+
+    // the transfer length is in half-words
+    assert_eq!(NDTR.read(), 8);
+
+    // the PSIZE (bits 8 and 9) and MSIZE (bits 10 and 11) fields are set to 16 bits
+    assert_eq!(CR.read() & 0b1111 << 8, 0b0101 << 8);
+}
+
+// UNCHANGED
+
+/// A singleton that represents serial port #1
+pub struct Serial1 {
+    dma: Dma1Channel1,
+    // ..
+}
+
+impl<B> Transfer<B> {
+    /// Returns `true` if the DMA transfer has finished
+    pub fn is_done(&self) -> bool {
+        !Dma1Channel1::in_progress()
+    }
+
+    /// Blocks until the transfer is done and returns the buffer
+    pub fn wait(mut self) -> (Pin<B>, Serial1) {
+        while !self.is_done() {}
+
+        atomic::compiler_fence(Ordering::Acquire);
+
+        let inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { hint::unreachable_unchecked() });
+        (inner.buffer, inner.serial)
+    }
+}
+
+impl<B> Drop for Transfer<B> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.as_mut() {
+            // NOTE: this is a volatile write
+            inner.serial.dma.stop();
+
+            // we need a read here to make the Acquire fence effective
+            unsafe {
+                ptr::read_volatile(&0);
+            }
+
+            atomic::compiler_fence(Ordering::Acquire);
+        }
+    }
+}
@@ -1,12 +1,18 @@
 #![allow(unused_variables)]
 
-pub const USART1_TX: usize = 0x4000_0000;
-pub const USART1_RX: usize = 0x4000_0004;
+use core::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+pub const USART1_TX: PeripheralAddress = PeripheralAddress::new(0x4000_0000);
+pub const USART1_RX: PeripheralAddress = PeripheralAddress::new(0x4000_0004);
 
 /// A singleton that represents a single DMA channel (channel 1 in this case)
 ///
 /// This singleton has exclusive access to the registers of the DMA channel 1
 pub struct Dma1Channel1 {
+    _0: (),
     // ..
 }
 
@@ -16,8 +22,11 @@ impl Dma1Channel1 {
     /// `inc` indicates whether the address will be incremented after every byte transfer
     ///
     /// NOTE this performs a volatile write
-    pub fn set_destination_address(&mut self, address: usize, inc: bool) {
-        // ..
+    pub fn set_destination_address<A: Address>(&mut self, address: A, inc: bool) {
+        let address = address.get();
+        debug_assert_eq!(address % Self::required_alignment(self.transfer_width()), 0);
+        registers::dma1_channel1::DAR.write(address);
+        registers::dma1_channel1::CR.modify(|cr| with_destination_increment(cr, inc));
     }
 
     /// Data will be read from this `address`
@@ -25,22 +34,27 @@ impl Dma1Channel1 {
     /// `inc` indicates whether the address will be incremented after every byte transfer
     ///
     /// NOTE this performs a volatile write
-    pub fn set_source_address(&mut self, address: usize, inc: bool) {
-        // ..
+    pub fn set_source_address<A: Address>(&mut self, address: A, inc: bool) {
+        let address = address.get();
+        debug_assert_eq!(address % Self::required_alignment(self.transfer_width()), 0);
+        registers::dma1_channel1::SAR.write(address);
+        registers::dma1_channel1::CR.modify(|cr| with_source_increment(cr, inc));
     }
 
-    /// Number of bytes to transfer
+    /// Number of units, of the configured `TransferWidth`, to transfer
     ///
     /// NOTE this performs a volatile write
     pub fn set_transfer_length(&mut self, len: usize) {
-        // ..
+        registers::dma1_channel1::NDTR.write(transfer_count(len));
     }
 
     /// Starts the DMA transfer
     ///
-    /// NOTE this performs a volatile write
+    /// NOTE this performs a read-modify-write on the configuration register
     pub fn start(&mut self) {
-        // ..
+        registers::dma1_channel1::CR.modify(with_enable);
+        // NOTE nothing moves data on the host; the simulated transfer is done as soon as it starts
+        registers::dma1_channel1::ISR.modify(|isr| isr | ISR_TCIF);
     }
 
     /// Stops the DMA transfer
@@ -54,13 +68,14 @@ impl Dma1Channel1 {
     ///
     /// NOTE this performs a volatile read
     pub fn in_progress() -> bool {
-        // ..
-        false
+        let isr = registers::dma1_channel1::ISR.read();
+        registers::dma1_channel1::CR.read() & CR_EN != 0 && isr & ISR_TCIF == 0
     }
 }
 
 /// A singleton that represents serial port #1
 pub struct Serial1 {
+    _0: (),
     // ..
 }
 
@@ -69,17 +84,852 @@ impl Serial1 {
     ///
     /// NOTE: blocks if no byte is available to be read
     pub fn read(&mut self) -> Result<u8, Error> {
-        // ..
-        Ok(0)
+        // .. wait until a byte is available ..
+
+        if let Some(error) = status_error(self.status()) {
+            return Err(error);
+        }
+
+        let byte = registers::serial1::DR.read();
+        Ok(byte as u8)
     }
 
     /// Sends out a single byte
     ///
     /// NOTE: blocks if the output FIFO buffer is full
     pub fn write(&mut self, byte: u8) -> Result<(), Error> {
+        // a DMA transfer is sending data out
+        if self.is_tx_busy() {
+            return Err(Error::Overrun);
+        }
+
+        registers::serial1::DR.write(usize::from(byte));
+        Ok(())
+    }
+}
+
+/// Serial port error
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Error {
+    /// A byte was received before the previous one was read out; or, on `write`, the transmitter
+    /// is in use by a DMA transfer
+    Overrun,
+    /// The stop bit of the received byte was not detected
+    Framing,
+    /// The parity bit of the received byte doesn't match its data
+    Parity,
+    /// Noise was detected on the line while receiving the byte
+    Noise,
+    /// No byte was received, or the output FIFO didn't drain, within the cycle budget; see
+    /// `Serial1::read_timeout`
+    Timeout,
+}
+
+// Loopback enable bit of the control register 1
+const CR1_LBE: usize = 1 << 14;
+
+impl Serial1 {
+    /// Enables (`on = true`) or disables (`on = false`) the internal loopback mode
+    ///
+    /// In loopback mode the output of the transmitter is internally connected to the input of the
+    /// receiver so every byte sent out is received back by this same serial port. The TX and RX
+    /// pins are bypassed, which makes this mode useful to test the serial port without any external
+    /// wiring.
+    ///
+    /// NOTE this performs a read-modify-write on the control register 1
+    pub fn set_loopback(&mut self, on: bool) {
+        registers::serial1::CR1.modify(|cr1| if on { cr1 | CR1_LBE } else { cr1 & !CR1_LBE });
+    }
+}
+
+/// Size of the unit of data moved by the DMA on each transfer
+///
+/// NOTE `set_transfer_length` counts units of this size, not bytes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransferWidth {
+    /// 8 bits
+    Byte = 0b00,
+    /// 16 bits
+    HalfWord = 0b01,
+    /// 32 bits
+    Word = 0b10,
+}
+
+// Size fields of the configuration register; the peripheral (PSIZE) and memory (MSIZE) sides are
+// always set to the same width
+const CR_PSIZE_OFFSET: usize = 8;
+const CR_MSIZE_OFFSET: usize = 10;
+const CR_SIZE_MASK: usize = 0b11;
+
+impl Dma1Channel1 {
+    /// Returns the alignment, in bytes, that the source and destination addresses must have when
+    /// transferring units of the given `width`
+    ///
+    /// On strict silicon a misaligned address makes the DMA transfer fault (bus error) instead of
+    /// silently rounding the address. `set_source_address` and `set_destination_address` check the
+    /// alignment against the configured transfer width in debug builds.
+    pub fn required_alignment(width: TransferWidth) -> usize {
+        match width {
+            TransferWidth::Byte => 1,
+            TransferWidth::HalfWord => 2,
+            TransferWidth::Word => 4,
+        }
+    }
+
+    /// Units of data of this `width` will be transferred
+    ///
+    /// NOTE this performs a read-modify-write on the configuration register
+    pub fn set_transfer_width(&mut self, width: TransferWidth) {
+        let size = width as usize;
+        let mask = CR_SIZE_MASK << CR_PSIZE_OFFSET | CR_SIZE_MASK << CR_MSIZE_OFFSET;
+
+        registers::dma1_channel1::CR
+            .modify(|cr| (cr & !mask) | size << CR_PSIZE_OFFSET | size << CR_MSIZE_OFFSET);
+    }
+
+    /// Returns the configured transfer width
+    ///
+    /// NOTE this performs a volatile read
+    pub fn transfer_width(&self) -> TransferWidth {
+        match (registers::dma1_channel1::CR.read() >> CR_MSIZE_OFFSET) & CR_SIZE_MASK {
+            0b00 => TransferWidth::Byte,
+            0b01 => TransferWidth::HalfWord,
+            _ => TransferWidth::Word,
+        }
+    }
+}
+
+impl Dma1Channel1 {
+    /// Fills the given `buffer` with `value`
+    ///
+    /// The DMA repeatedly reads a one-byte source buffer (no address increment) and writes it to
+    /// the destination (address incremented after every byte). The source byte lives in a `static`
+    /// owned by this channel so it stays alive, and unchanged, for as long as the returned `Fill`
+    /// borrows the channel. `buffer` is moved into the `Fill` and handed back by `Fill::wait`; if
+    /// the `Fill` is leaked the buffer stays leaked with it so the DMA never writes to memory that
+    /// is in use.
+    pub fn fill(&mut self, buffer: &'static mut [u8], value: u8) -> Fill<'_> {
+        static mut VALUE: u8 = 0;
+
+        unsafe { core::ptr::write_volatile(core::ptr::addr_of_mut!(VALUE), value) }
+
+        self.set_transfer_width(TransferWidth::Byte);
+        self.set_source_address(MemoryAddress::from_ptr(core::ptr::addr_of!(VALUE)), false);
+        self.set_destination_address(MemoryAddress::from_ptr(buffer.as_mut_ptr()), true);
+        self.set_transfer_length(buffer.len());
+
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::Release);
+        self.start();
+
+        Fill {
+            buffer: Some(buffer),
+            dma: self,
+        }
+    }
+}
+
+/// An in-progress DMA fill; see `Dma1Channel1::fill`
+pub struct Fill<'a> {
+    buffer: Option<&'static mut [u8]>,
+    dma: &'a mut Dma1Channel1,
+}
+
+impl Fill<'_> {
+    /// Returns `true` if the DMA fill has finished
+    pub fn is_done(&self) -> bool {
+        !Dma1Channel1::in_progress()
+    }
+
+    /// Blocks until the fill is done and returns the filled buffer
+    pub fn wait(mut self) -> &'static mut [u8] {
+        while !self.is_done() {}
+
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::Acquire);
+
+        // NOTE(unwrap) `buffer` is only taken here, and `wait` consumes `self`
+        self.buffer.take().unwrap()
+    }
+}
+
+impl Drop for Fill<'_> {
+    fn drop(&mut self) {
+        // the transfer may still be in progress if `wait` was not called
+        if self.buffer.is_some() {
+            self.dma.stop();
+
+            core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::Acquire);
+        }
+    }
+}
+
+// More fields of the configuration register
+const CR_SINC: usize = 1 << 6;
+const CR_DINC: usize = 1 << 7;
+
+/// Returns the configuration register value `cr` with the source address increment bit set
+/// (`inc = true`) or cleared (`inc = false`)
+fn with_source_increment(cr: usize, inc: bool) -> usize {
+    if inc {
+        cr | CR_SINC
+    } else {
+        cr & !CR_SINC
+    }
+}
+
+/// Returns the configuration register value `cr` with the destination address increment bit set
+/// (`inc = true`) or cleared (`inc = false`)
+fn with_destination_increment(cr: usize, inc: bool) -> usize {
+    if inc {
+        cr | CR_DINC
+    } else {
+        cr & !CR_DINC
+    }
+}
+
+/// Returns the value of the 16-bit transfer count register for a transfer of `len` units
+///
+/// # Panics
+///
+/// If `len` doesn't fit in the register
+fn transfer_count(len: usize) -> usize {
+    assert!(len <= usize::from(u16::MAX), "DMA transfer too long");
+
+    len
+}
+
+/// A type that the DMA can move as a single unit of data
+///
+/// # Safety
+///
+/// `WIDTH` must match the size of the type
+pub unsafe trait Word: Copy {
+    /// The transfer width used to move values of this type
+    const WIDTH: TransferWidth;
+}
+
+unsafe impl Word for u8 {
+    const WIDTH: TransferWidth = TransferWidth::Byte;
+}
+
+unsafe impl Word for u16 {
+    const WIDTH: TransferWidth = TransferWidth::HalfWord;
+}
+
+unsafe impl Word for u32 {
+    const WIDTH: TransferWidth = TransferWidth::Word;
+}
+
+// Flags of the interrupt status register
+const ISR_TCIF: usize = 1 << 1;
+const ISR_TEIF: usize = 1 << 3;
+
+impl Dma1Channel1 {
+    /// Enables (`circular = true`) or disables circular mode
+    ///
+    /// In circular mode the transfer never finishes: after transferring `len` units the addresses
+    /// and the transfer length are reloaded and the transfer starts over
+    ///
+    /// NOTE this performs a read-modify-write on the configuration register
+    pub fn set_circular(&mut self, circular: bool) {
+        // ..
+    }
+
+    /// Returns `true` if the first half of the transfer is done (half-transfer flag)
+    ///
+    /// NOTE this performs a volatile read
+    pub fn is_half_transfer_complete(&self) -> bool {
+        // ..
+        false
+    }
+
+    /// Returns `true` if the whole transfer is done (transfer-complete flag)
+    ///
+    /// NOTE this performs a volatile read
+    pub fn is_transfer_complete(&self) -> bool {
+        registers::dma1_channel1::ISR.read() & ISR_TCIF != 0
+    }
+
+    /// Clears the half-transfer flag
+    ///
+    /// NOTE this performs a volatile write
+    pub fn clear_half_transfer(&mut self) {
+        // ..
+    }
+
+    /// Clears the transfer-complete flag
+    ///
+    /// NOTE this performs a read-modify-write on the interrupt status register
+    pub fn clear_transfer_complete(&mut self) {
+        registers::dma1_channel1::ISR.modify(|isr| isr & !ISR_TCIF);
+    }
+}
+
+impl Dma1Channel1 {
+    /// Enables (`on = true`) or disables double buffer mode
+    ///
+    /// In double buffer mode the transfer never finishes: each time `len` units have been
+    /// transferred the channel switches to the other memory buffer (memory 0 is set with
+    /// `set_destination_address` / `set_source_address`, memory 1 with `set_memory1_address`) and
+    /// starts over
+    ///
+    /// NOTE this performs a read-modify-write on the configuration register
+    pub fn set_double_buffer(&mut self, on: bool) {
+        // ..
+    }
+
+    /// Memory 1 (the second buffer) in double buffer mode
+    ///
+    /// NOTE this performs a volatile write
+    pub fn set_memory1_address(&mut self, address: MemoryAddress) {
+        let address = address.get();
+        debug_assert_eq!(address % Self::required_alignment(self.transfer_width()), 0);
+        // ..
+    }
+
+    /// Returns the memory buffer, `0` or `1`, that the channel is currently using in double buffer
+    /// mode (current target bit)
+    ///
+    /// NOTE this performs a volatile read
+    pub fn current_target(&self) -> usize {
+        // ..
+        0
+    }
+}
+
+/// DMA transfer error
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DmaError {
+    /// A bus error occurred while accessing the source or destination address
+    Transfer,
+}
+
+impl Dma1Channel1 {
+    /// Returns `true` if the channel reported a transfer error (transfer error flag)
+    ///
+    /// NOTE the hardware disables the channel when a transfer error occurs so `in_progress` returns
+    /// `false` after that
+    ///
+    /// NOTE this performs a volatile read
+    pub fn error(&self) -> bool {
+        registers::dma1_channel1::ISR.read() & ISR_TEIF != 0
+    }
+
+    /// Clears the transfer error flag
+    ///
+    /// NOTE this performs a read-modify-write on the interrupt status register
+    pub fn clear_error(&mut self) {
+        registers::dma1_channel1::ISR.modify(|isr| isr & !ISR_TEIF);
+    }
+}
+
+impl Dma1Channel1 {
+    /// Returns the number of units that are left to transfer (count-down register)
+    ///
+    /// NOTE this performs a volatile read
+    pub fn remaining(&self) -> usize {
+        // ..
+        0
+    }
+}
+
+impl Dma1Channel1 {
+    /// Configures a memory to memory transfer of `len` bytes from `src` to `dst`
+    ///
+    /// This sets the memory to memory bit and enables the increment of both addresses. The transfer
+    /// must still be started with `start`
+    ///
+    /// NOTE this performs volatile writes
+    pub fn mem_to_mem(&mut self, src: *const u8, dst: *mut u8, len: usize) {
+        // .. set the memory to memory bit ..
+        self.set_source_address(MemoryAddress::from_ptr(src), true);
+        self.set_destination_address(MemoryAddress::from_ptr(dst), true);
+        self.set_transfer_length(len);
+    }
+}
+
+/// A DMA channel
+///
+/// Abstracts over the registers of the different DMA channels
+pub trait DmaChannel {
+    /// Data will be written to this `address`; see `Dma1Channel1::set_destination_address`
+    fn set_destination_address<A: Address>(&mut self, address: A, inc: bool);
+
+    /// Data will be read from this `address`; see `Dma1Channel1::set_source_address`
+    fn set_source_address<A: Address>(&mut self, address: A, inc: bool);
+
+    /// Number of units to transfer; see `Dma1Channel1::set_transfer_length`
+    fn set_transfer_length(&mut self, len: usize);
+
+    /// Starts the DMA transfer
+    fn start(&mut self);
+
+    /// Stops the DMA transfer
+    fn stop(&mut self);
+
+    /// Returns `true` if there's a transfer in progress
+    fn in_progress() -> bool;
+}
+
+impl DmaChannel for Dma1Channel1 {
+    fn set_destination_address<A: Address>(&mut self, address: A, inc: bool) {
+        Dma1Channel1::set_destination_address(self, address, inc)
+    }
+
+    fn set_source_address<A: Address>(&mut self, address: A, inc: bool) {
+        Dma1Channel1::set_source_address(self, address, inc)
+    }
+
+    fn set_transfer_length(&mut self, len: usize) {
+        Dma1Channel1::set_transfer_length(self, len)
+    }
+
+    fn start(&mut self) {
+        Dma1Channel1::start(self)
+    }
+
+    fn stop(&mut self) {
+        Dma1Channel1::stop(self)
+    }
+
+    fn in_progress() -> bool {
+        Dma1Channel1::in_progress()
+    }
+}
+
+/// A singleton that represents the DMA channel 2
+///
+/// This singleton has exclusive access to the registers of the DMA channel 2
+pub struct Dma1Channel2 {
+    _0: (),
+    // ..
+}
+
+// NOTE all these methods perform a volatile write / read on the registers of channel 2
+impl DmaChannel for Dma1Channel2 {
+    fn set_destination_address<A: Address>(&mut self, address: A, inc: bool) {
+        // ..
+    }
+
+    fn set_source_address<A: Address>(&mut self, address: A, inc: bool) {
         // ..
+    }
+
+    fn set_transfer_length(&mut self, len: usize) {
+        // ..
+    }
+
+    fn start(&mut self) {
+        // ..
+    }
+
+    fn stop(&mut self) {
+        // ..
+    }
+
+    fn in_progress() -> bool {
+        // ..
+        false
+    }
+}
+
+impl Dma1Channel1 {
+    /// Enables the half transfer interrupt
+    ///
+    /// The interrupt fires when the half-transfer flag gets set; see `is_half_transfer_complete`
+    ///
+    /// NOTE this performs a read-modify-write on the configuration register
+    pub fn enable_half_transfer_interrupt(&mut self) {
+        // ..
+    }
+}
+
+impl Serial1 {
+    /// Enables the idle line interrupt
+    ///
+    /// The interrupt fires when the line stays idle for one frame after receiving a byte, i.e. at
+    /// the end of a burst of data; see `is_idle`
+    ///
+    /// NOTE this performs a read-modify-write on the control register
+    pub fn enable_idle_interrupt(&mut self) {
+        // ..
+    }
+
+    /// Disables the idle line interrupt
+    ///
+    /// NOTE this performs a read-modify-write on the control register
+    pub fn disable_idle_interrupt(&mut self) {
+        // ..
+    }
+
+    /// Returns `true` if an idle line has been detected (idle flag)
+    ///
+    /// NOTE this performs a volatile read
+    pub fn is_idle(&self) -> bool {
+        // ..
+        false
+    }
+
+    /// Clears the idle flag
+    ///
+    /// NOTE this performs a volatile write
+    pub fn clear_idle(&mut self) {
+        // ..
+    }
+}
+
+/// An address that the DMA can read data from or write data to
+///
+/// See `PeripheralAddress` and `MemoryAddress`
+pub trait Address: Copy {
+    /// Returns the address as an integer
+    fn get(self) -> usize;
+}
+
+/// The address of a peripheral register, e.g. the data register of a serial port
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PeripheralAddress(usize);
+
+impl PeripheralAddress {
+    /// Wraps the address of a peripheral register
+    pub const fn new(address: usize) -> Self {
+        PeripheralAddress(address)
+    }
+}
+
+impl Address for PeripheralAddress {
+    fn get(self) -> usize {
+        self.0
+    }
+}
+
+/// The address of some memory, e.g. a buffer in RAM
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MemoryAddress(usize);
+
+impl MemoryAddress {
+    /// Returns the address that `ptr` points to
+    pub fn from_ptr<T>(ptr: *const T) -> Self {
+        MemoryAddress(ptr as usize)
+    }
+}
+
+impl Address for MemoryAddress {
+    fn get(self) -> usize {
+        self.0
+    }
+}
+
+impl Dma1Channel1 {
+    /// Configures a transfer of `len` units from the peripheral register at `src` to the memory at
+    /// `dst`
+    ///
+    /// Only the memory address is incremented. The transfer must still be started with `start`
+    ///
+    /// NOTE this performs volatile writes
+    pub fn peripheral_to_memory(&mut self, src: PeripheralAddress, dst: MemoryAddress, len: usize) {
+        self.set_source_address(src, false);
+        self.set_destination_address(dst, true);
+        self.set_transfer_length(len);
+    }
+
+    /// Configures a transfer of `len` units from the memory at `src` to the peripheral register at
+    /// `dst`
+    ///
+    /// Only the memory address is incremented. The transfer must still be started with `start`
+    ///
+    /// NOTE this performs volatile writes
+    pub fn memory_to_peripheral(&mut self, src: MemoryAddress, dst: PeripheralAddress, len: usize) {
+        self.set_source_address(src, true);
+        self.set_destination_address(dst, false);
+        self.set_transfer_length(len);
+    }
+}
+
+// Flags of the status register
+const SR_RXNE: usize = 1 << 5;
+const SR_TXE: usize = 1 << 7;
+
+impl Serial1 {
+    /// Reads out a single byte, if one is available
+    ///
+    /// NOTE this performs a volatile read
+    pub fn try_read(&mut self) -> Option<u8> {
+        if registers::serial1::SR.read() & SR_RXNE == 0 {
+            None
+        } else {
+            Some(registers::serial1::DR.read() as u8)
+        }
+    }
+
+    /// Sends out a single byte, if there's room in the output FIFO buffer
+    ///
+    /// Returns `false` if the FIFO is full
+    ///
+    /// NOTE this performs a volatile read and, if there's room, a volatile write
+    pub fn try_write(&mut self, byte: u8) -> bool {
+        if registers::serial1::SR.read() & SR_TXE == 0 {
+            false
+        } else {
+            registers::serial1::DR.write(usize::from(byte));
+            true
+        }
+    }
+
+    /// Reads out a single byte, giving up after `cycles` clock cycles
+    ///
+    /// NOTE the cycle counter of the DWT must be enabled
+    pub fn read_timeout(&mut self, cycles: u32) -> Result<u8, Error> {
+        poll_timeout(cycles, cycle_count, || self.try_read())
+    }
+
+    /// Sends out a single byte, giving up after `cycles` clock cycles
+    ///
+    /// NOTE the cycle counter of the DWT must be enabled
+    pub fn write_timeout(&mut self, byte: u8, cycles: u32) -> Result<(), Error> {
+        poll_timeout(cycles, cycle_count, || {
+            if self.try_write(byte) {
+                Some(())
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Returns the value of the DWT cycle counter
+///
+/// NOTE this performs a volatile read
+fn cycle_count() -> u32 {
+    // NOTE the simulated counter doesn't run on its own; advance it as if some cycles had elapsed
+    // since the previous read
+    registers::dwt::CYCCNT.modify(|cyccnt| (cyccnt as u32).wrapping_add(10) as usize);
+
+    registers::dwt::CYCCNT.read() as u32
+}
+
+/// Calls `poll` until it returns `Some` or until `cycles` clock cycles, as reported by `now`, have
+/// elapsed
+///
+/// NOTE the elapsed time is computed with wrapping arithmetic so the counter may overflow once
+fn poll_timeout<T>(
+    cycles: u32,
+    mut now: impl FnMut() -> u32,
+    mut poll: impl FnMut() -> Option<T>,
+) -> Result<T, Error> {
+    let start = now();
+
+    loop {
+        if let Some(value) = poll() {
+            return Ok(value);
+        }
+
+        if now().wrapping_sub(start) >= cycles {
+            return Err(Error::Timeout);
+        }
+    }
+}
+
+// Error flags of the status register
+const SR_PE: usize = 1 << 0;
+const SR_FE: usize = 1 << 1;
+const SR_NE: usize = 1 << 2;
+const SR_ORE: usize = 1 << 3;
+
+// DMA transmitter enable bit of the control register 3
+const CR3_DMAT: usize = 1 << 7;
+
+impl Serial1 {
+    /// Returns the value of the status register
+    ///
+    /// NOTE this performs a volatile read. Reading the status register and then the data register
+    /// clears the error flags
+    fn status(&self) -> usize {
+        registers::serial1::SR.read()
+    }
+
+    /// Returns `true` if the transmitter is in use by a DMA transfer (DMA transmitter enable bit)
+    ///
+    /// NOTE this performs a volatile read
+    fn is_tx_busy(&self) -> bool {
+        registers::serial1::CR3.read() & CR3_DMAT != 0
+    }
+}
+
+/// Returns the reception error flagged in the status register value `sr`, if any
+///
+/// If several errors are flagged the overrun is reported first
+fn status_error(sr: usize) -> Option<Error> {
+    if sr & SR_ORE != 0 {
+        Some(Error::Overrun)
+    } else if sr & SR_FE != 0 {
+        Some(Error::Framing)
+    } else if sr & SR_PE != 0 {
+        Some(Error::Parity)
+    } else if sr & SR_NE != 0 {
+        Some(Error::Noise)
+    } else {
+        None
+    }
+}
+
+/// Priority level of a DMA channel
+///
+/// When several channels have pending requests the controller serves the one with the highest
+/// priority level first
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Priority {
+    /// Low priority
+    Low = 0b00,
+    /// Medium priority
+    Medium = 0b01,
+    /// High priority
+    High = 0b10,
+    /// Very high priority
+    VeryHigh = 0b11,
+}
+
+// Fields of the configuration register
+const CR_EN: usize = 1 << 0;
+const CR_PL_OFFSET: usize = 12;
+const CR_PL_MASK: usize = 0b11 << CR_PL_OFFSET;
+
+impl Dma1Channel1 {
+    /// Sets the priority level (PL bits) of the channel
+    ///
+    /// Use this when a low latency channel must win the arbitration against other channels
+    ///
+    /// NOTE this performs a read-modify-write on the configuration register
+    pub fn set_priority(&mut self, p: Priority) {
+        registers::dma1_channel1::CR.modify(|cr| with_priority(cr, p));
+    }
+}
+
+/// Returns the configuration register value `cr` with its priority level set to `p`
+fn with_priority(cr: usize, p: Priority) -> usize {
+    (cr & !CR_PL_MASK) | ((p as usize) << CR_PL_OFFSET)
+}
+
+/// Returns the configuration register value `cr` with the channel enable bit set
+fn with_enable(cr: usize) -> usize {
+    cr | CR_EN
+}
+
+/// The peripherals used in these examples
+///
+/// This is the only way to get an instance of the `Dma1Channel1`, `Dma1Channel2` and `Serial1`
+/// singletons
+#[allow(non_snake_case)]
+pub struct Peripherals {
+    /// DMA channel 1
+    pub DMA1_CHANNEL1: Dma1Channel1,
+    /// DMA channel 2
+    pub DMA1_CHANNEL2: Dma1Channel2,
+    /// Serial port #1
+    pub SERIAL1: Serial1,
+}
+
+impl Peripherals {
+    /// Returns all the peripherals *once*; later calls return `None`
+    pub fn take() -> Option<Self> {
+        static TAKEN: AtomicBool = AtomicBool::new(false);
+
+        if TAKEN.swap(true, Ordering::Relaxed) {
+            None
+        } else {
+            Some(Peripherals {
+                DMA1_CHANNEL1: Dma1Channel1 { _0: () },
+                DMA1_CHANNEL2: Dma1Channel2 { _0: () },
+                SERIAL1: Serial1 { _0: () },
+            })
+        }
+    }
+}
+
+/// Formatted output: `write!(serial, "x = {}", x)`
+///
+/// NOTE this blocks until all the bytes have been sent out
+impl fmt::Write for Serial1 {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        // a serial error aborts the formatting
+        for byte in s.bytes() {
+            self.write(byte).map_err(|_| fmt::Error)?;
+        }
+
         Ok(())
     }
 }
 
-pub enum Error {}
+/// Simulated registers
+///
+/// These examples run on the host, which has neither a DMA controller nor a serial port, so the
+/// singletons read and write these registers instead. The examples inspect them to check what a
+/// method has programmed
+#[doc(hidden)]
+pub mod registers {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A memory mapped register
+    ///
+    /// NOTE this holds a `usize`, rather than a `u32`, so that the address registers can hold host
+    /// addresses
+    pub struct Register(AtomicUsize);
+
+    impl Register {
+        const fn new(reset_value: usize) -> Self {
+            Register(AtomicUsize::new(reset_value))
+        }
+
+        /// Stands in for a volatile read
+        pub fn read(&self) -> usize {
+            self.0.load(Ordering::SeqCst)
+        }
+
+        /// Stands in for a volatile write
+        pub fn write(&self, value: usize) {
+            self.0.store(value, Ordering::SeqCst)
+        }
+
+        /// Stands in for a read-modify-write
+        pub fn modify(&self, f: impl FnOnce(usize) -> usize) {
+            self.write(f(self.read()))
+        }
+    }
+
+    /// Registers of the DMA channel 1
+    pub mod dma1_channel1 {
+        use super::Register;
+
+        /// Configuration register
+        pub static CR: Register = Register::new(0);
+        /// Transfer count register
+        pub static NDTR: Register = Register::new(0);
+        /// Source address register
+        pub static SAR: Register = Register::new(0);
+        /// Destination address register
+        pub static DAR: Register = Register::new(0);
+        /// Interrupt status register
+        pub static ISR: Register = Register::new(0);
+    }
+
+    /// Registers of the serial port #1
+    pub mod serial1 {
+        use super::Register;
+
+        /// Status register; the transmitter starts out empty (TXE)
+        pub static SR: Register = Register::new(1 << 7);
+        /// Data register
+        pub static DR: Register = Register::new(0);
+        /// Control register 1
+        pub static CR1: Register = Register::new(0);
+        /// Control register 3
+        pub static CR3: Register = Register::new(0);
+    }
+
+    /// Registers of the Data Watchpoint and Trace unit
+    pub mod dwt {
+        use super::Register;
+
+        /// Cycle counter
+        pub static CYCCNT: Register = Register::new(0);
+    }
+}
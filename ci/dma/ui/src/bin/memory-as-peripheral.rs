@@ -0,0 +1,14 @@
+use shared::{Dma1Channel1, MemoryAddress};
+
+static mut BUFFER: [u8; 16] = [0; 16];
+static mut OTHER: [u8; 16] = [0; 16];
+
+fn send(dma: &mut Dma1Channel1) {
+    let src = MemoryAddress::from_ptr(unsafe { core::ptr::addr_of!(BUFFER) });
+    let dst = MemoryAddress::from_ptr(unsafe { core::ptr::addr_of!(OTHER) });
+
+    // error: expected `PeripheralAddress`, found `MemoryAddress`
+    dma.memory_to_peripheral(src, dst, 16);
+}
+
+fn main() {}
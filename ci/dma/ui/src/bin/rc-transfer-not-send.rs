@@ -0,0 +1,18 @@
+use std::rc::Rc;
+
+// the `Transfer` of `examples/send.rs`
+#[path = "../../../examples/send.rs"]
+mod send;
+
+use send::Transfer;
+
+fn assert_send<T>()
+where
+    T: Send,
+{
+}
+
+fn main() {
+    // error: cannot be sent between threads safely
+    assert_send::<Transfer<Rc<[u8; 16]>>>();
+}
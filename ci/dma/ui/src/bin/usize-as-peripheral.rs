@@ -0,0 +1,12 @@
+use shared::{Dma1Channel1, MemoryAddress};
+
+static mut BUFFER: [u8; 16] = [0; 16];
+
+fn receive(dma: &mut Dma1Channel1) {
+    let dst = MemoryAddress::from_ptr(unsafe { core::ptr::addr_of!(BUFFER) });
+
+    // error: expected `PeripheralAddress`, found integer
+    dma.peripheral_to_memory(0x4000_0004, dst, 16);
+}
+
+fn main() {}
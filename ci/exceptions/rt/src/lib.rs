@@ -42,13 +42,22 @@ fn panic(_panic: &PanicInfo<'_>) -> ! {
 
 #[macro_export]
 macro_rules! entry {
-    ($path:path) => {
+    // NOTE `tt`s, unlike a `path` fragment, keep their spans so errors point at the user's `main`
+    ($($path:tt)+) => {
         #[export_name = "main"]
         pub unsafe fn __main() -> ! {
-            // type check the given path
-            let f: fn() -> ! = $path;
+            // `main` must be a `fn() -> !`: no arguments, no type parameters and it must diverge
+            const MAIN: fn() -> ! = $($path)+;
 
-            f()
+            MAIN()
+        }
+
+        // a second `entry!` invocation anywhere in the crate is rejected with "the name
+        // `__entry_point_defined_multiple_times` is defined multiple times"
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! __entry_point_defined_multiple_times {
+            () => {};
         }
     }
 }
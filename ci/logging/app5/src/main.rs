@@ -0,0 +1,43 @@
+#![no_main]
+#![no_std]
+
+use cortex_m_semihosting::{
+    debug,
+    hio::{self, HStdout},
+};
+
+use log::{error, info, trace, warn, Log};
+use rt::entry;
+
+entry!(main);
+
+fn main() -> ! {
+    let hstdout = hio::hstdout().unwrap();
+    let mut logger = Logger { hstdout };
+
+    // compiled out: this crate depends on `log` with the `level-warn` feature. The disabled macro
+    // still returns a `Result` so `unwrap` works the same at every log level
+    trace!(logger, "Entering main").unwrap();
+
+    info!(logger, "Hello, world!").unwrap();
+
+    warn!(logger, "Low battery").unwrap();
+
+    error!(logger, "Goodbye").unwrap();
+
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
+
+struct Logger {
+    hstdout: HStdout,
+}
+
+impl Log for Logger {
+    type Error = ();
+
+    fn log(&mut self, address: u8) -> Result<(), ()> {
+        self.hstdout.write_all(&[address])
+    }
+}
@@ -0,0 +1,49 @@
+#![no_main]
+#![no_std]
+
+use cortex_m_semihosting::{
+    debug,
+    hio::{self, HStdout},
+};
+
+use log::{error, info, trace, warn, Log};
+use rt::entry;
+
+entry!(main);
+
+fn main() -> ! {
+    let hstdout = hio::hstdout().unwrap();
+    let mut logger = Logger { hstdout };
+
+    // logged in reverse order of severity; the linker still puts the errors first
+    let _ = trace!(logger, "Entering main");
+
+    let _ = info!(logger, "Hello, world!");
+
+    let _ = warn!(logger, "Low battery");
+
+    let _ = error!(logger, "Goodbye");
+
+    // a message without a log level
+    #[export_name = "Bye"]
+    #[link_section = ".log"]
+    static BYE: u8 = 0;
+
+    let _ = logger.log(&BYE as *const u8 as usize as u8);
+
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
+
+struct Logger {
+    hstdout: HStdout,
+}
+
+impl Log for Logger {
+    type Error = ();
+
+    fn log(&mut self, address: u8) -> Result<(), ()> {
+        self.hstdout.write_all(&[address])
+    }
+}
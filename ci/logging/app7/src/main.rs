@@ -0,0 +1,45 @@
+#![no_main]
+#![no_std]
+
+use cortex_m_semihosting::debug;
+
+use log::{log, Log};
+use rt::entry;
+
+struct Logger;
+
+impl Log for Logger {
+    type Error = ();
+
+    fn log(&mut self, _address: u8) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+// logs `"message 000"`, `"message 001"`, ..., `"message 299"`
+macro_rules! log_many {
+    ($logger:ident; $($h:tt)*) => {
+        $(log_many!(@tens $logger, $h; 0 1 2 3 4 5 6 7 8 9);)*
+    };
+
+    (@tens $logger:ident, $h:tt; $($t:tt)*) => {
+        $(log_many!(@ones $logger, $h, $t; 0 1 2 3 4 5 6 7 8 9);)*
+    };
+
+    (@ones $logger:ident, $h:tt, $t:tt; $($o:tt)*) => {
+        $(let _ = log!($logger, concat!("message ", $h, $t, $o));)*
+    };
+}
+
+entry!(main);
+
+// NOTE this program must NOT link: 300 strings don't fit in the 256 addresses a byte can encode
+fn main() -> ! {
+    let mut logger = Logger;
+
+    log_many!(logger; 0 1 2);
+
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
@@ -0,0 +1,144 @@
+//! Host side decoder for the logs produced by the `log` crates
+//!
+//! The device only transmits the addresses of the symbols that live in the `.log` sections; the
+//! strings themselves are the names of those symbols so they can be recovered from the ELF file.
+
+use std::{collections::BTreeMap, convert::TryInto};
+
+// 32-bit ELF constants
+const ELFCLASS32: u8 = 1;
+const ELFDATA2LSB: u8 = 1;
+const SHT_SYMTAB: u32 = 2;
+const STT_OBJECT: u8 = 1;
+
+/// Turns the `stream` of addresses received from the device back into log messages
+///
+/// `elf` is the contents of the ELF file that's running on the device. Addresses that don't match
+/// any log symbol are rendered as `<unknown 0x..>`.
+///
+/// # Panics
+///
+/// This function panics if `elf` is not a 32-bit little endian ELF file
+pub fn decode(elf: &[u8], stream: &[u8]) -> Vec<String> {
+    let symbols = symbols(elf).expect("not a 32-bit little endian ELF file");
+
+    stream
+        .iter()
+        .map(|address| match symbols.get(&u16::from(*address)) {
+            Some(string) => string.clone(),
+            None => format!("<unknown {:#04x}>", address),
+        })
+        .collect()
+}
+
+/// Like `decode` but the device sends each address as a little-endian `u16`
+///
+/// A trailing odd byte, e.g. from a truncated capture, is ignored.
+///
+/// # Panics
+///
+/// This function panics if `elf` is not a 32-bit little endian ELF file
+pub fn decode_u16(elf: &[u8], stream: &[u8]) -> Vec<String> {
+    let symbols = symbols(elf).expect("not a 32-bit little endian ELF file");
+
+    stream
+        .chunks_exact(2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .map(|address| match symbols.get(&address) {
+            Some(string) => string.clone(),
+            None => format!("<unknown {:#06x}>", address),
+        })
+        .collect()
+}
+
+/// Returns the address -> string map of the log symbols in the `.log` (and `.log.*`) sections
+///
+/// Returns `None` if `elf` is not a (well formed) 32-bit little endian ELF file
+pub fn symbols(elf: &[u8]) -> Option<BTreeMap<u16, String>> {
+    if elf.get(..4)? != b"\x7fELF" || *elf.get(4)? != ELFCLASS32 || *elf.get(5)? != ELFDATA2LSB {
+        return None;
+    }
+
+    let shoff = u32_at(elf, 0x20)? as usize;
+    let shentsize = u16_at(elf, 0x2e)? as usize;
+    let shnum = u16_at(elf, 0x30)? as usize;
+    let shstrndx = u16_at(elf, 0x32)? as usize;
+
+    let sections = (0..shnum)
+        .map(|i| Section::parse(elf, shoff + i * shentsize))
+        .collect::<Option<Vec<_>>>()?;
+    let shstrtab = sections.get(shstrndx)?;
+
+    let mut map = BTreeMap::new();
+    for symtab in sections.iter().filter(|section| section.kind == SHT_SYMTAB) {
+        let strtab = sections.get(symtab.link as usize)?;
+
+        // NOTE the first entry is the null symbol
+        for offset in (symtab.offset..symtab.offset + symtab.size)
+            .step_by(symtab.entsize.max(1))
+            .skip(1)
+        {
+            let name = u32_at(elf, offset)?;
+            let value = u32_at(elf, offset + 4)?;
+            let info = *elf.get(offset + 12)?;
+            let shndx = u16_at(elf, offset + 14)? as usize;
+
+            // skip markers like `__log_warning_start__`; they are not messages
+            if info & 0xf != STT_OBJECT {
+                continue;
+            }
+
+            let section = match sections.get(shndx) {
+                Some(section) => section,
+                None => continue,
+            };
+            let section_name = str_at(elf, shstrtab, section.name)?;
+            if section_name != ".log" && !section_name.starts_with(".log.") {
+                continue;
+            }
+
+            map.insert(value as u16, str_at(elf, strtab, name)?.to_owned());
+        }
+    }
+
+    Some(map)
+}
+
+struct Section {
+    name: u32,
+    kind: u32,
+    offset: usize,
+    size: usize,
+    link: u32,
+    entsize: usize,
+}
+
+impl Section {
+    fn parse(elf: &[u8], offset: usize) -> Option<Self> {
+        Some(Section {
+            name: u32_at(elf, offset)?,
+            kind: u32_at(elf, offset + 4)?,
+            offset: u32_at(elf, offset + 16)? as usize,
+            size: u32_at(elf, offset + 20)? as usize,
+            link: u32_at(elf, offset + 24)?,
+            entsize: u32_at(elf, offset + 36)? as usize,
+        })
+    }
+}
+
+fn u16_at(elf: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(elf.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+fn u32_at(elf: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(elf.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+// reads the NUL terminated string at `index` of the string table `strtab`
+fn str_at<'a>(elf: &'a [u8], strtab: &Section, index: u32) -> Option<&'a str> {
+    let start = strtab.offset + index as usize;
+    let bytes = elf.get(start..strtab.offset + strtab.size)?;
+    let end = bytes.iter().position(|b| *b == 0)?;
+
+    std::str::from_utf8(&bytes[..end]).ok()
+}
@@ -0,0 +1,32 @@
+//! Usage: `decoder [--u16] <elf> <stream>`
+//!
+//! Prints the log messages contained in the `stream` file, e.g. the captured semihosting output, one
+//! per line. With `--u16` the addresses in the stream are 2 bytes wide (little-endian).
+
+use std::{env, fs, process};
+
+fn main() {
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+    let wide = args.first().map(|arg| arg == "--u16").unwrap_or(false);
+    if wide {
+        args.remove(0);
+    }
+
+    if args.len() != 2 {
+        eprintln!("usage: decoder [--u16] <elf> <stream>");
+        process::exit(1);
+    }
+
+    let elf = fs::read(&args[0]).expect("couldn't read the ELF file");
+    let stream = fs::read(&args[1]).expect("couldn't read the stream");
+
+    let messages = if wide {
+        decoder::decode_u16(&elf, &stream)
+    } else {
+        decoder::decode(&elf, &stream)
+    };
+
+    for message in messages {
+        println!("{}", message);
+    }
+}
@@ -1,6 +1,12 @@
 SECTIONS
 {
   .log 0 (INFO) : {
+    _slog = .;
     *(.log);
+    _elog = .;
   }
 }
+
+/* The addresses of the interned strings are sent as single bytes */
+ASSERT(_elog - _slog <= 256, "
+ERROR(log): too many interned strings; their addresses don't fit in a `u8`");
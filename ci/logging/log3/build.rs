@@ -0,0 +1 @@
+../log/build.rs
\ No newline at end of file
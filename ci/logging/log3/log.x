@@ -0,0 +1,30 @@
+/* The messages are sorted by severity so the level of a message can be inferred from its address */
+SECTIONS
+{
+  .log 0 (INFO) : {
+    _slog_error = .;
+    *(.log.error);
+    _elog_error = .;
+
+    _slog_warning = .;
+    *(.log.warning);
+    _elog_warning = .;
+
+    _slog_info = .;
+    *(.log.info);
+    _elog_info = .;
+
+    _slog_trace = .;
+    *(.log.trace);
+    _elog_trace = .;
+
+    /* messages without a log level */
+    _slog = .;
+    *(.log);
+    _elog = .;
+  }
+}
+
+/* The addresses of the messages, of all the levels, are sent as single bytes */
+ASSERT(_elog - _slog_error <= 256, "
+ERROR(log): too many interned strings; their addresses don't fit in a `u8`");
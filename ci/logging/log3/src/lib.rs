@@ -0,0 +1,100 @@
+#![no_std]
+
+pub trait Log {
+    type Error;
+
+    fn log(&mut self, address: u8) -> Result<(), Self::Error>;
+}
+
+// Expansion of the disabled log macros; it has the same type as an enabled one so the call sites
+// compile at every log level
+#[doc(hidden)]
+pub fn __disabled<L: Log>(_: &mut L) -> Result<(), L::Error> {
+    Ok(())
+}
+
+/// Logs messages at the ERROR log level
+#[macro_export]
+macro_rules! error {
+    ($logger:expr, $string:expr) => {{
+        #[export_name = $string]
+        #[link_section = ".log.error"]
+        static SYMBOL: u8 = 0;
+
+        $crate::Log::log(&mut $logger, &SYMBOL as *const u8 as usize as u8)
+    }};
+}
+
+/// Logs messages at the WARNING log level
+#[cfg(not(feature = "level-error"))]
+#[macro_export]
+macro_rules! warn {
+    ($logger:expr, $string:expr) => {{
+        #[export_name = $string]
+        #[link_section = ".log.warning"]
+        static SYMBOL: u8 = 0;
+
+        $crate::Log::log(&mut $logger, &SYMBOL as *const u8 as usize as u8)
+    }};
+}
+
+/// Logs messages at the WARNING log level (disabled)
+#[cfg(feature = "level-error")]
+#[macro_export]
+macro_rules! warn {
+    ($logger:expr, $string:expr) => {
+        $crate::__disabled(&mut $logger)
+    };
+}
+
+/// Logs messages at the INFO log level
+#[cfg(not(any(feature = "level-error", feature = "level-warn")))]
+#[macro_export]
+macro_rules! info {
+    ($logger:expr, $string:expr) => {{
+        #[export_name = $string]
+        #[link_section = ".log.info"]
+        static SYMBOL: u8 = 0;
+
+        $crate::Log::log(&mut $logger, &SYMBOL as *const u8 as usize as u8)
+    }};
+}
+
+/// Logs messages at the INFO log level (disabled)
+#[cfg(any(feature = "level-error", feature = "level-warn"))]
+#[macro_export]
+macro_rules! info {
+    ($logger:expr, $string:expr) => {
+        $crate::__disabled(&mut $logger)
+    };
+}
+
+/// Logs messages at the TRACE log level
+#[cfg(not(any(
+    feature = "level-error",
+    feature = "level-warn",
+    feature = "level-info"
+)))]
+#[macro_export]
+macro_rules! trace {
+    ($logger:expr, $string:expr) => {{
+        #[export_name = $string]
+        #[link_section = ".log.trace"]
+        static SYMBOL: u8 = 0;
+
+        $crate::Log::log(&mut $logger, &SYMBOL as *const u8 as usize as u8)
+    }};
+}
+
+/// Logs messages at the TRACE log level (disabled)
+#[cfg(any(
+    feature = "level-error",
+    feature = "level-warn",
+    feature = "level-info"
+))]
+#[macro_export]
+macro_rules! trace {
+    ($logger:expr, $string:expr) => {
+        $crate::__disabled(&mut $logger)
+    };
+}
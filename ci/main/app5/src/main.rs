@@ -0,0 +1,38 @@
+#![no_main]
+#![no_std]
+
+use core::{arch::asm, ptr};
+
+use rt::{entry, init_prelude};
+
+init_prelude!(prelude);
+
+entry!(main);
+
+static mut BSS: u32 = 0;
+static mut DATA: u32 = 1;
+static mut STEP: u32 = 0;
+
+unsafe fn prelude() {
+    rt::zero_bss();
+
+    // user step: `.bss` is ready but `.data` is not
+    ptr::write_volatile(ptr::addr_of_mut!(STEP), 1);
+
+    rt::init_data();
+}
+
+fn main() -> ! {
+    unsafe {
+        // check that the custom prelude initialized RAM and ran the user step
+        if ptr::read_volatile(ptr::addr_of!(BSS)) != 0
+            || ptr::read_volatile(ptr::addr_of!(DATA)) != 1
+            || ptr::read_volatile(ptr::addr_of!(STEP)) != 1
+        {
+            // this makes QEMU crash
+            asm!("BKPT");
+        }
+    }
+
+    loop {}
+}
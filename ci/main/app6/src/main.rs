@@ -0,0 +1,22 @@
+#![no_main]
+#![no_std]
+
+use core::{arch::asm, ptr};
+
+use rt::entry;
+
+entry!(main);
+
+static mut DATA: u32 = 1;
+
+fn main() -> ! {
+    unsafe {
+        // check that the `init-ram` feature initialized DATA
+        if ptr::read_volatile(ptr::addr_of!(DATA)) != 1 {
+            // this makes QEMU crash
+            asm!("BKPT");
+        }
+    }
+
+    loop {}
+}
@@ -0,0 +1,28 @@
+#![no_main]
+#![no_std]
+
+use core::{arch::asm, ptr};
+
+use rt::entry;
+
+entry!(main);
+
+// a RAM address that lies outside `.bss`, `.data` and the (small) stack of this program
+const SENTINEL: *mut u32 = 0x2000_8000 as *mut u32;
+
+#[no_mangle]
+pub unsafe extern "C" fn __pre_init() {
+    ptr::write_volatile(SENTINEL, 0xDEAD_BEEF);
+}
+
+fn main() -> ! {
+    unsafe {
+        // check that `__pre_init` ran and that RAM initialization left the sentinel alone
+        if ptr::read_volatile(SENTINEL) != 0xDEAD_BEEF {
+            // this makes QEMU crash
+            asm!("BKPT");
+        }
+    }
+
+    loop {}
+}
@@ -0,0 +1,43 @@
+#![no_main]
+#![no_std]
+
+use core::{arch::asm, ptr};
+
+use rt::{entry, uninit};
+
+const MAGIC: u32 = 0xC0FF_EE42;
+
+// SysTick Reload Value Register; `Reset` doesn't touch it so we use it to count boots
+const SYST_RVR: *mut u32 = 0xE000_E014 as *mut u32;
+
+static mut ZEROED: u32 = 0;
+
+uninit!(static mut PRESERVED: u32);
+
+entry!(main);
+
+fn main() -> ! {
+    unsafe {
+        // `Reset` must zero `.bss` on every boot
+        if ptr::read_volatile(ptr::addr_of!(ZEROED)) != 0 {
+            // this makes QEMU crash
+            asm!("BKPT");
+        }
+
+        if ptr::read_volatile(SYST_RVR) == 0 {
+            // first boot
+            ptr::write_volatile(SYST_RVR, 1);
+
+            ptr::write_volatile(ptr::addr_of_mut!(ZEROED), 1);
+            ptr::write_volatile(ptr::addr_of_mut!(PRESERVED).cast::<u32>(), MAGIC);
+
+            // simulated warm reset
+            rt::Reset()
+        } else if ptr::read_volatile(ptr::addr_of!(PRESERVED).cast::<u32>()) != MAGIC {
+            // `Reset` must leave `.uninit` alone
+            asm!("BKPT");
+        }
+    }
+
+    loop {}
+}
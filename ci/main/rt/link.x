@@ -39,6 +39,7 @@ SECTIONS
   } > RAM
 
   .data :
+  AT(ADDR(.rodata) + SIZEOF(.rodata))
   {
     *(.data .data.*);
   } > RAM
@@ -47,4 +48,19 @@ SECTIONS
   {
     *(.ARM.exidx .ARM.exidx.*);
   }
+
+  /* Not initialized by `Reset`: the contents of this section survive a reset; see `uninit!` */
+  .uninit (NOLOAD) : ALIGN(4)
+  {
+    *(.uninit .uninit.*);
+  } > RAM
 }
+
+/* Used by `Reset` to initialize RAM when the `init-ram` feature is enabled */
+_sbss = ADDR(.bss);
+_ebss = ADDR(.bss) + SIZEOF(.bss);
+_sdata = ADDR(.data);
+_edata = ADDR(.data) + SIZEOF(.data);
+_sidata = LOADADDR(.data);
+
+PROVIDE(__pre_init = DefaultPreInit);
@@ -5,10 +5,25 @@ use core::panic::PanicInfo;
 // CHANGED!
 #[no_mangle]
 pub unsafe extern "C" fn Reset() -> ! {
+    extern "C" {
+        /// Hook that runs before RAM is initialized; defaults to `DefaultPreInit`
+        ///
+        /// # Safety
+        ///
+        /// It runs before `.bss` and `.data` are initialized so it must not access any `static`
+        /// variable: reads return garbage and writes will be overwritten. Only the stack can be used.
+        fn __pre_init();
+    }
+
     extern "Rust" {
         fn main() -> !;
     }
 
+    __pre_init();
+
+    #[cfg(feature = "init-ram")]
+    init_ram();
+
     main()
 }
 
@@ -24,13 +39,80 @@ fn panic(_panic: &PanicInfo<'_>) -> ! {
 
 #[macro_export]
 macro_rules! entry {
-    ($path:path) => {
+    // NOTE `tt`s, unlike a `path` fragment, keep their spans so errors point at the user's `main`
+    ($($path:tt)+) => {
         #[export_name = "main"]
         pub unsafe fn __main() -> ! {
-            // type check the given path
-            let f: fn() -> ! = $path;
+            // `main` must be a `fn() -> !`: no arguments, no type parameters and it must diverge
+            const MAIN: fn() -> ! = $($path)+;
 
-            f()
+            MAIN()
         }
+
+        // a second `entry!` invocation anywhere in the crate is rejected with "the name
+        // `__entry_point_defined_multiple_times` is defined multiple times"
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! __entry_point_defined_multiple_times {
+            () => {};
+        }
+    }
+}
+
+/// Zeroes `.bss` and then initializes `.data`
+///
+/// `Reset` calls this after `__pre_init` and before `main` when the `init-ram` feature is enabled,
+/// which is the default. Without it `static` variables hold whatever happened to be in RAM at boot.
+#[cfg(feature = "init-ram")]
+unsafe fn init_ram() {
+    use core::ptr;
+
+    extern "C" {
+        static mut _sbss: u8;
+        static mut _ebss: u8;
+
+        static mut _sdata: u8;
+        static mut _edata: u8;
+        static _sidata: u8;
     }
+
+    let count = ptr::addr_of!(_ebss) as usize - ptr::addr_of!(_sbss) as usize;
+    ptr::write_bytes(ptr::addr_of_mut!(_sbss), 0, count);
+
+    let count = ptr::addr_of!(_edata) as usize - ptr::addr_of!(_sdata) as usize;
+    ptr::copy_nonoverlapping(ptr::addr_of!(_sidata), ptr::addr_of_mut!(_sdata), count);
+}
+
+/// The default `__pre_init`, which does nothing; see `link.x`
+///
+/// Override it to run code before RAM is initialized, e.g. to configure the Flash wait states or an
+/// external RAM controller:
+///
+/// ``` ignore
+/// #[no_mangle]
+/// pub unsafe extern "C" fn __pre_init() {
+///     // ..
+/// }
+/// ```
+///
+/// See `Reset` for what an override may and may not do.
+#[no_mangle]
+pub extern "C" fn DefaultPreInit() {}
+
+/// Declares a `static mut` variable that `Reset` doesn't initialize
+///
+/// The variable is placed in the `.uninit` section, which lies outside `.bss` and `.data`, so its
+/// contents survive a reset that doesn't power cycle the device. After a power-on reset, however, it
+/// contains garbage so its type is wrapped in a `MaybeUninit`:
+///
+/// ``` ignore
+/// rt::uninit!(static mut CRASH_DATA: [u8; 64]);
+/// ```
+#[macro_export]
+macro_rules! uninit {
+    ($(#[$attr:meta])* $vis:vis static mut $name:ident: $ty:ty) => {
+        $(#[$attr])*
+        #[link_section = ".uninit"]
+        $vis static mut $name: ::core::mem::MaybeUninit<$ty> = ::core::mem::MaybeUninit::uninit();
+    };
 }
@@ -54,3 +54,5 @@ SECTIONS
     *(.ARM.exidx .ARM.exidx.*);
   }
 }
+
+PROVIDE(__init_prelude = DefaultInitPrelude);
@@ -7,27 +7,70 @@ use core::ptr;
 pub unsafe extern "C" fn Reset() -> ! {
     // NEW!
     // Initialize RAM
+    extern "C" {
+        fn __init_prelude();
+    }
+
+    __init_prelude();
+
+    // Call user entry point
+    extern "Rust" {
+        fn main() -> !;
+    }
+
+    main()
+}
+
+/// Zeroes the `.bss` section
+///
+/// # Safety
+///
+/// Must be called at most once, before any `static` variable that lives in `.bss` is used.
+pub unsafe fn zero_bss() {
     extern "C" {
         static mut _sbss: u8;
         static mut _ebss: u8;
+    }
+
+    let count = &_ebss as *const u8 as usize - &_sbss as *const u8 as usize;
+    ptr::write_bytes(&mut _sbss as *mut u8, 0, count);
+}
 
+/// Copies the initial values of the `.data` section from Flash to RAM
+///
+/// # Safety
+///
+/// Must be called at most once, before any `static` variable that lives in `.data` is used.
+pub unsafe fn init_data() {
+    extern "C" {
         static mut _sdata: u8;
         static mut _edata: u8;
         static _sidata: u8;
     }
 
-    let count = &_ebss as *const u8 as usize - &_sbss as *const u8 as usize;
-    ptr::write_bytes(&mut _sbss as *mut u8, 0, count);
-
     let count = &_edata as *const u8 as usize - &_sdata as *const u8 as usize;
     ptr::copy_nonoverlapping(&_sidata as *const u8, &mut _sdata as *mut u8, count);
+}
 
-    // Call user entry point
-    extern "Rust" {
-        fn main() -> !;
-    }
+/// Initializes RAM: zeroes `.bss` and then initializes `.data`
+///
+/// # Safety
+///
+/// Must be called at most once, before any `static` variable that lives in `.bss` or `.data` is
+/// used.
+pub unsafe fn init_ram() {
+    zero_bss();
+    init_data();
+}
 
-    main()
+/// The default initialization sequence; see `link.x`
+///
+/// # Safety
+///
+/// Only `Reset` may call this, once, before `main`; see `init_ram`.
+#[no_mangle]
+pub unsafe extern "C" fn DefaultInitPrelude() {
+    init_ram()
 }
 
 // The reset vector, a pointer into the reset handler
@@ -42,13 +85,44 @@ fn panic(_panic: &PanicInfo<'_>) -> ! {
 
 #[macro_export]
 macro_rules! entry {
-    ($path:path) => {
+    // NOTE `tt`s, unlike a `path` fragment, keep their spans so errors point at the user's `main`
+    ($($path:tt)+) => {
         #[export_name = "main"]
         pub unsafe fn __main() -> ! {
+            // `main` must be a `fn() -> !`: no arguments, no type parameters and it must diverge
+            const MAIN: fn() -> ! = $($path)+;
+
+            MAIN()
+        }
+
+        // a second `entry!` invocation anywhere in the crate is rejected with "the name
+        // `__entry_point_defined_multiple_times` is defined multiple times"
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! __entry_point_defined_multiple_times {
+            () => {};
+        }
+    }
+}
+
+/// Replaces the RAM initialization sequence that `Reset` runs before `main`
+///
+/// The given function must call `zero_bss` and `init_data` (or `init_ram`) itself. The stack pointer
+/// has already been set by the hardware when it runs so any order works, with these caveats:
+///
+/// - Code that runs before `zero_bss` / `init_data` must not read `static` variables that live in
+///   `.bss` / `.data`; their contents are still undefined at that point.
+/// - Writes to those `static` variables are only kept if they happen *after* the section they live
+///   in has been initialized; otherwise `zero_bss` / `init_data` will overwrite them.
+#[macro_export]
+macro_rules! init_prelude {
+    ($path:path) => {
+        #[export_name = "__init_prelude"]
+        pub unsafe extern "C" fn __init_prelude() {
             // type check the given path
-            let f: fn() -> ! = $path;
+            let f: unsafe fn() = $path;
 
             f()
         }
-    }
+    };
 }
@@ -0,0 +1,21 @@
+//! This must NOT compile
+
+#![no_std]
+#![no_main]
+
+mod a {
+    rt::entry!(main);
+
+    fn main() -> ! {
+        loop {}
+    }
+}
+
+mod b {
+    // error: the name `__entry_point_defined_multiple_times` is defined multiple times
+    rt::entry!(main);
+
+    fn main() -> ! {
+        loop {}
+    }
+}
@@ -0,0 +1,13 @@
+//! This must NOT compile
+
+#![no_std]
+#![no_main]
+
+use rt::entry;
+
+// error: cannot infer type of the type parameter `T` declared on the function `main`
+entry!(main);
+
+fn main<T>() -> ! {
+    loop {}
+}
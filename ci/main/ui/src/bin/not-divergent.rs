@@ -0,0 +1,11 @@
+//! This must NOT compile
+
+#![no_std]
+#![no_main]
+
+use rt::entry;
+
+// error: expected fn pointer `fn() -> !`
+entry!(main);
+
+fn main() {}
@@ -0,0 +1,13 @@
+//! This must NOT compile
+
+#![no_std]
+#![no_main]
+
+use rt::entry;
+
+// error: incorrect number of function parameters
+entry!(main);
+
+fn main(_x: u32) -> ! {
+    loop {}
+}
@@ -0,0 +1,37 @@
+#!/bin/bash
+
+# Checks that the reset vector, the second entry of the vector table, holds the address of `Reset`
+# with the thumb bit set. Prints both addresses; the text of the "memory layout" chapter cites them.
+#
+# Usage: `./check-reset.sh <elf>`; requires `rust-objdump` (`cargo-binutils`) or `llvm-objdump`
+
+set -euo pipefail
+
+main() {
+    local elf=$1
+
+    # address of the `Reset` symbol, e.g. `00000008`
+    local reset=$(objdump -t $elf | awk '$NF == "Reset" { print $1 }')
+
+    # second word of the vector table in little endian format, e.g. `09000000`
+    local vector=$(objdump -s -j .vector_table $elf | awk '$1 == "0000" { print $3 }')
+    vector=${vector:6:2}${vector:4:2}${vector:2:2}${vector:0:2}
+
+    printf 'Reset: 0x%x\nreset vector: 0x%x\n' $((16#$reset)) $((16#$vector))
+
+    if [ $((16#$vector)) != $((16#$reset | 1)) ]; then
+        echo "error: the reset vector doesn't point to Reset (thumb mode)"
+        exit 1
+    fi
+}
+
+# `rust-objdump` is installed by `cargo-binutils`
+objdump() {
+    if command -v rust-objdump >/dev/null; then
+        rust-objdump "$@"
+    else
+        llvm-objdump "$@"
+    fi
+}
+
+main "$@"
@@ -57,6 +57,9 @@ main() {
     diff -b app.vector_table.objdump \
          <(cargo objdump --bin app -- -s --section .vector_table)
 
+    # check that the reset vector points to `Reset`, whatever its address is
+    ./check-reset.sh target/thumbv7m-none-eabi/debug/app
+
     qemu_check target/thumbv7m-none-eabi/debug/app
 
     edition_check
@@ -85,6 +88,38 @@ main() {
     edition_check
     popd
 
+    pushd app5
+    cargo build
+    qemu_check target/thumbv7m-none-eabi/debug/app
+    edition_check
+    popd
+
+    # check that `rt`'s `init-ram` feature initializes `.data`
+    pushd app6
+    cargo build
+    qemu_check target/thumbv7m-none-eabi/debug/app
+    edition_check
+    popd
+
+    # check that a user-defined `__pre_init` runs before `main`
+    pushd app7
+    cargo build
+    qemu_check target/thumbv7m-none-eabi/debug/app
+    edition_check
+    popd
+
+    # check that `uninit!` statics survive a (simulated) warm reset while `.bss` is zeroed
+    pushd app8
+    cargo build
+    qemu_check target/thumbv7m-none-eabi/debug/app
+    edition_check
+    popd
+
+    # check that `entry!` rejects functions that are not `fn() -> !` and being invoked twice
+    pushd ui
+    ui_check
+    popd
+
     # NOTE(nightly) this will require nightly until core::arch::arm::udf is stabilized
     if [ $RUST_VERSION = nightly ]; then
         pushd app4
@@ -129,6 +164,9 @@ main() {
     diff release.vector_table \
          <(cargo objdump --bin app --release -- -s -j .vector_table)
     edition_check
+
+    # check that `rt` also builds for ARMv6-M (Cortex-M0)
+    RUSTFLAGS="-C link-arg=-Tlink.x" cargo build --target thumbv6m-none-eabi
     popd
 
     # check that the binary blob is up to date
@@ -143,11 +181,270 @@ main() {
     pushd app2
     diff -b release.objdump \
          <(cargo objdump --bin app --release -- -d --no-show-raw-insn --print-imm-hex --no-leading-addr)
+    diff release.vector_table \
+         <(cargo objdump --bin app --release -- -s -j .vector_table)
+    cargo objdump --bin app --release -- -t | grep HardFaultTrampoline
+    edition_check
+
+    # check the fallback to `librt.a` when `asm.s` can't be assembled
+    cargo clean
+    CC_thumbv7m_none_eabi=false cargo build --release
+    cargo objdump --bin app --release -- -t | grep HardFaultTrampoline
+    popd
+
+    # check that `backtrace` finds the return addresses in a synthetic stack
+    pushd app3
+    cargo build
+    qemu_check target/thumbv7m-none-eabi/debug/app
+    edition_check
+    popd
+
+    # check that the panic message survives a (simulated) reset
+    pushd app4
+    cargo build
+    qemu_check target/thumbv7m-none-eabi/debug/app
+    edition_check
+    popd
+
+    # check that an overridden `__boot_mode` is used by `boot_mode`
+    pushd app5
+    cargo build
+    qemu_check target/thumbv7m-none-eabi/debug/app
+    edition_check
+    popd
+
+    # check that `exception!` puts the user handler in the vector table
+    pushd app6
     diff release.vector_table \
          <(cargo objdump --bin app --release -- -s -j .vector_table)
     edition_check
     popd
 
+    # check that an application without exception handlers links and uses the default handler
+    pushd app7
+    diff release.vector_table \
+         <(cargo objdump --bin app --release -- -s -j .vector_table)
+    # without device specific interrupts the code starts right after the 16 core vectors
+    cargo nm --bin app --release | grep '^00000040 T _stext$'
+    edition_check
+    popd
+
+    # check that the `HardFault` handler gets the stacked registers
+    pushd app8
+    cargo run
+    edition_check
+    popd
+
+    # check that `interrupts!` / `interrupt!` populate the device specific part of the vector table
+    pushd app9
+    diff release.vector_table \
+         <(cargo objdump --bin app --release -- -s -j .vector_table)
+    # the 32 interrupt vectors move the start of the code by 128 bytes
+    cargo nm --bin app --release | grep '^000000c0 T _stext$'
+    edition_check
+    popd
+
+    # check that an interrupt handler registered at runtime (`vtor-ram`) gets called
+    pushd app10
+    cargo run
+    edition_check
+    popd
+
+    # check that the `fpu` feature enables the FPU before `main`
+    # NOTE(cargo build) the QEMU used in CI doesn't emulate the FPU of the Cortex-M4F so we can't
+    # `cargo run` this one
+    pushd app11
+    cargo build
+    edition_check
+    popd
+
+    # check that the heap region returned by `heap_start` / `heap_end` lies past `.bss`
+    pushd app12
+    cargo build
+    qemu_check target/thumbv7m-none-eabi/debug/app
+    edition_check
+    popd
+
+    # check that `stack_used` sees the stack grow
+    pushd app13
+    cargo build
+    qemu_check target/thumbv7m-none-eabi/debug/app
+    edition_check
+    popd
+
+    # check that `DefaultExceptionHandler` records the number of the exception it handled
+    pushd app14
+    cargo run
+    edition_check
+    popd
+
+    # check that the initial stack pointer can be overridden
+    pushd app15
+    diff release.vector_table \
+         <(cargo objdump --bin app --release -- -s -j .vector_table)
+    edition_check
+    popd
+
+    # check that `bind_interrupt!` binds the handler and accepts `Sync` resources
+    pushd app16
+    cargo run
+    edition_check
+    popd
+
+    # check that `bind_interrupt!` rejects resources that can't be shared with interrupt context and
+    # that `#[entry]` rejects invalid entry points
+    pushd ui
+    ui_check
+    popd
+
+    # check that the memory layout comes from the application's `memory.x`
+    pushd app17
+    diff release.vector_table \
+         <(cargo objdump --bin app --release -- -s -j .vector_table)
+    edition_check
+
+    # check that the link fails if there's not enough RAM left for the stack
+    sed -i 's/LENGTH = 16K/LENGTH = 512/' memory.x
+    touch src/main.rs
+    if cargo build 2>&1 | tee /dev/stderr | grep -q 'ERROR(rt): not enough RAM left for the stack'; then
+        git checkout memory.x
+    else
+        exit 1
+    fi
+    popd
+
+    # check that `stack_overflowed` detects writes into the stack guard region
+    pushd app18
+    cargo run
+    edition_check
+    popd
+
+    # check that `Reset` accepts an image whose CRC32 has been stored by the host `checksum` tool
+    pushd app19
+    cargo build
+    cargo run --manifest-path ../checksum/Cargo.toml -- target/thumbv7m-none-eabi/debug/app
+    qemu-system-arm \
+        -cpu cortex-m3 \
+        -machine lm3s6965evb \
+        -nographic \
+        -semihosting-config enable=on,target=native \
+        -kernel target/thumbv7m-none-eabi/debug/app
+    edition_check
+
+    # check that corrupting one byte of `.text` is detected
+    cp target/thumbv7m-none-eabi/debug/app corrupt.elf
+    offset=$(arm-none-eabi-readelf -S --wide corrupt.elf | \
+                 sed -n 's/.* \.text  *PROGBITS  *[0-9a-f]*  *\([0-9a-f]*\) .*/\1/p')
+    printf '\x00' | dd of=corrupt.elf bs=1 seek=$((16#$offset)) conv=notrunc
+    if cargo run --manifest-path ../checksum/Cargo.toml -- --verify corrupt.elf; then
+        exit 1
+    fi
+    rm corrupt.elf
+    popd
+
+    # check that `rt::sections` reports the boundaries of `.bss` and `.data`
+    pushd app20
+    cargo run
+    edition_check
+    popd
+
+    # check that `#[ramfunc]` functions are stored in Flash but run from RAM
+    pushd app21
+    cargo build
+    cargo objdump --bin app -- -t | grep double | grep '^2000'
+    cargo objdump --bin app -- -t | grep _siram_text | grep '^000'
+    cargo run
+    edition_check
+    popd
+
+    # check that `#[entry]` sets the entry point
+    pushd app22
+    cargo run
+    edition_check
+    popd
+
+    # check that `rt::exit(0)` makes QEMU exit with status 0
+    pushd app23
+    cargo run
+    edition_check
+    popd
+
+    # the HardFault trampoline must pass the frame from the stack that was in use: MSP ...
+    pushd app24
+    cargo run
+    edition_check
+    popd
+
+    # ... or PSP
+    pushd app25
+    cargo run
+    edition_check
+    popd
+
+    # image loaded into RAM: `Reset` sets up MSP and VTOR
+    pushd app26
+    cargo run
+    edition_check
+    popd
+
+    # `no-panic`: a panic-free app links ...
+    pushd app27
+    cargo run --release
+    edition_check
+    popd
+
+    # ... but one with a bounds check doesn't
+    pushd app28
+    if cargo build --release 2>build.stderr; then
+        exit 1
+    fi
+    grep rust_panic_is_reachable build.stderr
+    rm build.stderr
+    edition_check
+    popd
+
+    # `fault-report`: the cause of the HardFault is printed before exiting with a non-zero code
+    pushd app29
+    if cargo run >run.out 2>&1; then
+        exit 1
+    fi
+    grep "undefined instruction" run.out
+    rm run.out
+    edition_check
+    popd
+
+    # `sanitize-control`: `main` runs on MSP after jumping to `Reset` from PSP
+    pushd app30
+    cargo run
+    edition_check
+    popd
+
+    # image behind a 32K bootloader: the vector table lives at the offset and `Reset` points VTOR
+    # to it (`set-vtor`)
+    pushd app31
+    diff release.vector_table \
+         <(cargo objdump --bin app --release -- -s -j .vector_table)
+    cargo nm --bin app --release | grep '^00008004 R RESET_VECTOR$'
+    cargo objdump --bin app --release -- -d --no-show-raw-insn | \
+        grep -A8 '<Reset>:' | grep '#0x8000'
+    edition_check
+    popd
+
+    # check that `reset_reason` reports what the overridden `__reset_reason` returned in `Reset`
+    pushd app32
+    cargo run
+    edition_check
+    popd
+
+    # check that the compile time checks on `Vector` fire if it grows past 4 bytes
+    pushd rt
+    sed -i 's/^    reserved: u32,$/    reserved: u64,/' src/lib.rs
+    if cargo check --target thumbv7m-none-eabi; then
+        exit 1
+    fi
+    git checkout src/lib.rs
+    popd
+
     popd
 
     # # Logging with symbols
@@ -191,6 +488,9 @@ main() {
          <(cargo run | xxd -p)
     diff -b dev.objdump \
          <(cargo objdump --bin app -- -t | grep '\.log')
+    # the interned strings are kept in the ELF but they don't take space on the device: `.log` is
+    # not an allocated section (no `TEXT` / `DATA` / `BSS` type) and it starts at address 0
+    cargo objdump --bin app -- -h | grep -E '\.log +[0-9a-f]+ 00000000 *$'
     edition_check
     popd
 
@@ -212,6 +512,41 @@ main() {
     edition_check
     popd
 
+    # check that the messages below the `level-warn` log level are compiled out
+    pushd app5
+    diff dev.out \
+         <(cargo run | xxd -p)
+    diff -b dev.objdump \
+         <(cargo objdump --bin app -- -t | grep '\.log')
+    edition_check
+    popd
+
+    # check that the messages are sorted by severity, with the messages without a level last
+    pushd app6
+    diff dev.out \
+         <(cargo run | xxd -p)
+    diff -b dev.objdump \
+         <(cargo objdump --bin app -- -t | grep '\.log')
+    edition_check
+    popd
+
+    # check that interning more strings than a byte can address fails to link
+    pushd app7
+    if cargo build 2>build.stderr; then
+        exit 1
+    fi
+    grep "too many interned strings" build.stderr
+    rm build.stderr
+    edition_check
+    popd
+
+    # check that the host side decoder turns the output of `app4` back into text
+    pushd decoder
+    diff app4.out \
+         <(cargo run -- ../app4/target/thumbv7m-none-eabi/debug/app <(xxd -r -p ../app4/dev.out))
+    edition_check
+    popd
+
     popd
 
     # # Logging with symbols
@@ -224,9 +559,137 @@ main() {
          <(cargo objdump --bin app -- -t | grep '\.log')
     diff -b release.objdump \
          <(cargo objdump --bin app --release -- -t | grep LOGGER)
+    # the addresses are sent as little-endian `u16`s
+    diff <(printf 'Hello, world!\nGoodbye\n') \
+         <(cd ../../logging/decoder &&
+           cargo run -- --u16 ../../singleton/app/target/thumbv7m-none-eabi/debug/app \
+                     <(xxd -r -p ../../singleton/app/dev.out))
+    edition_check
+    popd
+
+    # check that more than 256 interned strings get different addresses
+    pushd app14
+    cargo run
+    edition_check
+    popd
+
+    # check that the logs written before the (simulated) reset are recovered
+    pushd app2
+    diff dev.out \
+         <(cargo run | xxd -p)
+    diff -b dev.objdump \
+         <(cargo objdump --bin app -- -t | grep '\.log')
+    edition_check
+    popd
+
+    # check that `Tee` sends every log to both loggers
+    pushd app3
+    diff dev.out \
+         <(cargo run | xxd -p)
+    diff -b dev.objdump \
+         <(cargo objdump --bin app -- -t | grep '\.log')
+    edition_check
+    popd
+
+    # check that the sent and dropped counters add up
+    pushd app4
+    diff dev.out \
+         <(cargo run | xxd -p)
+    edition_check
+    popd
+
+    # check that `RingLogger` doesn't lose the bytes logged from different priorities
+    pushd app5
+    diff dev.out \
+         <(cargo run | xxd -p)
+    diff -b dev.objdump \
+         <(cargo objdump --bin app -- -t | grep '\.log')
+    edition_check
+    popd
+
+    # check that the arguments of `log!` are sent, as little-endian bytes, after the format string
+    pushd app6
+    diff dev.out \
+         <(cargo run | xxd -p)
+    diff -b dev.objdump \
+         <(cargo objdump --bin app -- -t | grep '\.log')
     edition_check
     popd
 
+    # check that `log!` tags messages with strictly increasing timestamps
+    pushd app7
+    diff dev.out \
+         <(cargo run | xxd -p)
+    diff -b dev.objdump \
+         <(cargo objdump --bin app -- -t | grep '\.log')
+    edition_check
+    popd
+
+    # check that the `Drain` based loggers write the same bytes to semihosting and to memory
+    pushd app8
+    diff dev.out \
+         <(cargo run | xxd -p)
+    diff -b dev.objdump \
+         <(cargo objdump --bin app -- -t | grep '\.log')
+    edition_check
+    popd
+
+    # check that `ItmLogger` waits for the FIFO and then writes to the selected stimulus port
+    pushd app9
+    cargo run
+    edition_check
+    popd
+
+    # check that logging the same string from different modules sends the same address
+    pushd app10
+    diff dev.out \
+         <(cargo run | xxd -p)
+    diff -b dev.objdump \
+         <(cargo objdump --bin app -- -t | grep '\.log')
+    edition_check
+    popd
+
+    # check that `error!` / `warn!` / `info!` pass their level to the global logger
+    pushd app11
+    diff dev.out \
+         <(cargo run | xxd -p)
+    diff -b dev.objdump \
+         <(cargo objdump --bin app -- -t | grep '\.log')
+    edition_check
+    popd
+
+    # check that `BufferedDrain` only writes to semihosting once its buffer is full
+    pushd app12
+    diff dev.out \
+         <(cargo run | xxd -p)
+    diff -b dev.objdump \
+         <(cargo objdump --bin app -- -t | grep '\.log')
+    edition_check
+    popd
+
+    # check that `rt`'s `panic-log` feature logs the location of a panic
+    pushd app13
+    diff dev.out \
+         <(cargo run | xxd -p)
+    diff -b dev.objdump \
+         <(cargo objdump --bin app -- -t | grep '\.log')
+    edition_check
+    popd
+
+    # check that a failed `rt::assert!` logs the address of the string interned at its call site
+    pushd app15
+    diff dev.out \
+         <(cargo run | xxd -p)
+    diff -b dev.objdump \
+         <(cargo objdump --bin app -- -t | grep '\.log')
+    edition_check
+    popd
+
+    # check that `global_logger!` rejects loggers that are not `GlobalLog` and being invoked twice
+    pushd ui
+    ui_check
+    popd
+
     popd
 
     # # DMA
@@ -234,6 +697,52 @@ main() {
     if [ $RUST_VERSION = nightly ]; then
         pushd dma
         cargo build --examples
+        # check the length of a frame cut short by an idle line
+        cargo run --example idle
+        # check that the bounded waits give up once the cycle budget is spent
+        cargo run --example timeout
+        # check that `read` reports the errors flagged in the status register
+        cargo run --example status
+        # check that `join` waits for both transfers and `select` for the first one
+        cargo run --example join
+        # check the bit pattern that `set_priority` writes to the configuration register
+        cargo run --example priority
+        # check that the transmitter and receiver halves work from different threads
+        cargo run --example split
+        # check that the peripherals can only be taken once
+        cargo run --example take
+        # check that formatted output goes out through the serial port and stops at a serial error
+        cargo run --example fmt
+        # check the register values programmed by a DMA fill
+        cargo run --example fill
+        # check that `set_loopback` toggles the loopback bit and that the self-test catches corrupted bytes and errors
+        cargo run --example loopback
+        # check that the configured transfer width rejects misaligned addresses in debug builds
+        cargo run --example alignment
+        # check that a `[u16; 8]` transfer programs a length of 8 half-words
+        cargo run --example words
+        # check that an aborted transfer hands back its buffer
+        cargo run --example abort
+        # check which half of a circular buffer is handed out
+        cargo run --example circular
+        # check which of the double buffers is handed out
+        cargo run --example double
+        # check that `wait` reports a transfer error and still hands back the buffer
+        cargo run --example errors
+        # check that a memory to memory copy hands back both buffers
+        cargo run --example copy
+        # check that both DMA channels can drive the serial port transfers
+        cargo run --example channels
+        # check that a transfer can be completed from a different thread
+        cargo run --example send
+        # check that the interrupt handler wakes up the task awaiting a transfer
+        cargo run --example future
+
+        # check that the peripheral and memory sides of a transfer can't be mixed up and that a
+        # `Transfer<Rc<_>>` can't be sent to another thread
+        pushd ui
+        ui_check
+        popd
         popd
     fi
 }
@@ -243,6 +752,23 @@ edition_check() {
     RUSTFLAGS="-D rust_2018_compatibility -D rust_2018_idioms" cargo check
 }
 
+# checks that every program in `src/bin` fails to compile with the error given in its `// error:`
+# comment
+ui_check() {
+    for bin in src/bin/*.rs; do
+        local expected
+        expected=$(sed -n 's|.*// error: ||p' $bin)
+        [ -n "$expected" ]
+
+        if cargo check --bin $(basename $bin .rs) 2>.stderr; then
+            exit 1
+        fi
+
+        grep -qF "$expected" .stderr || ( cat .stderr && exit 1 )
+        rm .stderr
+    done
+}
+
 # checks that QEMU doesn't crash and that it produces no error messages
 qemu_check() {
     qemu-system-arm \
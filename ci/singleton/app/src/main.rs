@@ -27,7 +27,7 @@ fn main() -> ! {
 }
 
 impl GlobalLog for Logger {
-    fn log(&self, address: u8) {
+    fn log(&self, address: u16) {
         // we use a critical section (`interrupt::free`) to make the access to the
         // `static mut` variable interrupt safe which is required for memory safety
         interrupt::free(|_| unsafe {
@@ -40,7 +40,7 @@ impl GlobalLog for Logger {
 
             let hstdout = HSTDOUT.as_mut().unwrap();
 
-            hstdout.write_all(&[address])
+            hstdout.write_all(&address.to_le_bytes())
         }).ok(); // `.ok()` = ignore errors
     }
 }
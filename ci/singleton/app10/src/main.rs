@@ -0,0 +1,42 @@
+#![no_main]
+#![no_std]
+
+use cortex_m_semihosting::{debug, hio};
+
+use log::{global_logger, log, GlobalLog, Level};
+use rt::entry;
+
+struct Logger;
+
+global_logger!(Logger);
+
+entry!(main);
+
+fn main() -> ! {
+    log!("Hello, world!");
+
+    // the same string logged from another module is sent using the same address
+    farewell::log();
+
+    log!("Goodbye");
+
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
+
+mod farewell {
+    use log::log;
+
+    pub fn log() {
+        log!("Goodbye");
+    }
+}
+
+impl GlobalLog for Logger {
+    fn log(&self, _level: Level, address: u8) {
+        if let Ok(mut hstdout) = hio::hstdout() {
+            hstdout.write_all(&[address]).ok();
+        }
+    }
+}
@@ -0,0 +1,39 @@
+#![no_main]
+#![no_std]
+
+use cortex_m_semihosting::{debug, hio};
+
+use log::{error, global_logger, info, warn, GlobalLog, Level};
+use rt::entry;
+
+struct Logger;
+
+global_logger!(Logger);
+
+entry!(main);
+
+fn main() -> ! {
+    error!("Disk full");
+
+    warn!("Low battery");
+
+    info!("Battery voltage = {} mV", 3200_u16);
+
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
+
+impl GlobalLog for Logger {
+    fn log(&self, level: Level, address: u8) {
+        self.log_args(level, address, &[])
+    }
+
+    fn log_args(&self, level: Level, address: u8, args: &[u8]) {
+        if let Ok(mut hstdout) = hio::hstdout() {
+            // the level goes first, then the address and the arguments
+            hstdout.write_all(&[level as u8, address]).ok();
+            hstdout.write_all(args).ok();
+        }
+    }
+}
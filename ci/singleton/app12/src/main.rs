@@ -0,0 +1,51 @@
+#![no_main]
+#![no_std]
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use cortex_m_semihosting::{debug, hio};
+
+use log::{global_logger, log, BufferedDrain, Drain, GlobalLog, GlobalLogger, SemihostingDrain};
+use rt::entry;
+
+// each message takes 6 bytes (level + address + timestamp) so the buffer holds 8 messages
+static BUFFERED_LOGGER: GlobalLogger<BufferedDrain<Counter, 48>> =
+    GlobalLogger::new(BufferedDrain::new(Counter(SemihostingDrain::new())));
+
+global_logger!(BUFFERED_LOGGER);
+
+// number of semihosting writes
+static WRITES: AtomicU32 = AtomicU32::new(0);
+
+entry!(main);
+
+fn main() -> ! {
+    for _ in 0..8 {
+        log!("Hello, world!");
+    }
+
+    // nothing left in the buffer: this is a no-op
+    BUFFERED_LOGGER.flush();
+
+    if let Ok(mut hstdout) = hio::hstdout() {
+        hstdout
+            .write_all(&[WRITES.load(Ordering::Relaxed) as u8])
+            .ok();
+    }
+
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
+
+// A drain that counts the writes to the semihosting drain
+struct Counter(SemihostingDrain);
+
+impl Drain for Counter {
+    type Error = ();
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), ()> {
+        WRITES.fetch_add(1, Ordering::Relaxed);
+        self.0.write_bytes(bytes)
+    }
+}
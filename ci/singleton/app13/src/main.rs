@@ -0,0 +1,37 @@
+#![no_main]
+#![no_std]
+
+use cortex_m_semihosting::{debug, hio};
+
+use log::{global_logger, GlobalLog, Level};
+use rt::entry;
+
+struct Logger;
+
+global_logger!(Logger);
+
+entry!(main);
+
+fn main() -> ! {
+    panic!("Oops")
+}
+
+impl GlobalLog for Logger {
+    fn log(&self, level: Level, address: u8) {
+        self.log_args(level, address, &[])
+    }
+
+    fn log_args(&self, level: Level, address: u8, args: &[u8]) {
+        if let Ok(mut hstdout) = hio::hstdout() {
+            // NOTE the address of the file name, the last 4 bytes of the arguments, changes from
+            // build to build so we leave it out
+            hstdout.write_all(&[level as u8, address]).ok();
+            hstdout.write_all(&args[..args.len().min(4)]).ok();
+        }
+
+        // the panic handler is the only one that logs errors and it never returns
+        if level == Level::Error {
+            debug::exit(debug::EXIT_SUCCESS);
+        }
+    }
+}
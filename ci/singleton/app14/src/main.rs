@@ -0,0 +1,65 @@
+#![no_main]
+#![no_std]
+
+use cortex_m_semihosting::debug;
+
+use log::{log, Log};
+use rt::entry;
+
+const N: usize = 300;
+
+// records the addresses it's given
+struct Recorder {
+    addresses: [u16; N],
+    len: usize,
+}
+
+impl Log for Recorder {
+    type Error = ();
+
+    fn log(&mut self, address: u16) -> Result<(), ()> {
+        *self.addresses.get_mut(self.len).ok_or(())? = address;
+        self.len += 1;
+
+        Ok(())
+    }
+}
+
+// logs `"message 000"`, `"message 001"`, ..., `"message 299"`
+macro_rules! log_many {
+    ($logger:ident; $($h:tt)*) => {
+        $(log_many!(@tens $logger, $h; 0 1 2 3 4 5 6 7 8 9);)*
+    };
+
+    (@tens $logger:ident, $h:tt; $($t:tt)*) => {
+        $(log_many!(@ones $logger, $h, $t; 0 1 2 3 4 5 6 7 8 9);)*
+    };
+
+    (@ones $logger:ident, $h:tt, $t:tt; $($o:tt)*) => {
+        $(log!($logger, concat!("message ", $h, $t, $o)).unwrap();)*
+    };
+}
+
+entry!(main);
+
+fn main() -> ! {
+    let mut recorder = Recorder {
+        addresses: [0; N],
+        len: 0,
+    };
+
+    log_many!(recorder; 0 1 2);
+
+    // more than 256 strings but every one of them must have its own address
+    let addresses = &mut recorder.addresses[..recorder.len];
+    addresses.sort_unstable();
+    let unique = addresses.windows(2).all(|pair| pair[0] != pair[1]);
+
+    if recorder.len == N && unique {
+        debug::exit(debug::EXIT_SUCCESS);
+    } else {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+
+    loop {}
+}
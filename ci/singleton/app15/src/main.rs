@@ -0,0 +1,40 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m_semihosting::{debug, hio};
+
+use log::{global_logger, GlobalLog, Level};
+use rt::entry;
+
+struct Logger;
+
+global_logger!(Logger);
+
+entry!(main);
+
+fn main() -> ! {
+    // NOTE the volatile read hides the value from the compiler
+    let x = unsafe { ptr::read_volatile(&1) };
+
+    rt::assert_eq!(x, 1);
+    rt::assert!(x == 2);
+
+    debug::exit(debug::EXIT_FAILURE);
+
+    loop {}
+}
+
+impl GlobalLog for Logger {
+    fn log(&self, level: Level, address: u8) {
+        if let Ok(mut hstdout) = hio::hstdout() {
+            hstdout.write_all(&[level as u8, address]).ok();
+        }
+    }
+
+    // the failed assertion flushes the logger before halting
+    fn flush(&self) {
+        debug::exit(debug::EXIT_SUCCESS);
+    }
+}
@@ -0,0 +1,47 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m_semihosting::{debug, hio};
+
+use log::{global_logger, log, CrashLogger};
+use rt::entry;
+
+// these survive a reset
+#[link_section = ".uninit.CRASH_LOGGER"]
+static CRASH_LOGGER: CrashLogger<8> = CrashLogger::new();
+
+#[link_section = ".uninit.REBOOTED"]
+static mut REBOOTED: u32 = 0;
+
+const MAGIC: u32 = 0xDEAD_BEEF;
+
+global_logger!(CRASH_LOGGER);
+
+entry!(main);
+
+fn main() -> ! {
+    unsafe {
+        if ptr::read_volatile(ptr::addr_of!(REBOOTED)) != MAGIC {
+            ptr::write_volatile(ptr::addr_of_mut!(REBOOTED), MAGIC);
+
+            log!("Hello, world!");
+
+            log!("Goodbye");
+
+            // simulate a reset
+            rt::Reset();
+        }
+
+        ptr::write_volatile(ptr::addr_of_mut!(REBOOTED), 0);
+
+        // print what was logged before the reset
+        let mut hstdout = hio::hstdout().unwrap();
+        hstdout.write_all(CRASH_LOGGER.dump()).unwrap();
+    }
+
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
@@ -0,0 +1 @@
+../../asm/app/memory.x
\ No newline at end of file
@@ -0,0 +1,64 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m::interrupt;
+use cortex_m_semihosting::{
+    debug,
+    hio::{self, HStdout},
+};
+
+use log::{global_logger, log, CrashLogger, GlobalLog, Level, Tee};
+use rt::entry;
+
+// the ring buffer can only hold two messages
+#[link_section = ".uninit.CRASH_LOGGER"]
+static CRASH_LOGGER: CrashLogger<2> = CrashLogger::new();
+
+static LIVE_LOGGER: Logger = Logger;
+
+struct Logger;
+
+global_logger!(Tee::new(&CRASH_LOGGER, &LIVE_LOGGER));
+
+entry!(main);
+
+fn main() -> ! {
+    log!("Hello, world!");
+
+    log!("Goodbye");
+
+    log!("Farewell");
+
+    // the live sink got all three messages; now print the two that are left in the ring buffer
+    interrupt::free(|_| unsafe {
+        if let Ok(mut hstdout) = hio::hstdout() {
+            hstdout.write_all(CRASH_LOGGER.dump()).ok();
+        }
+    });
+
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
+
+impl GlobalLog for Logger {
+    fn log(&self, _level: Level, address: u8) {
+        // we use a critical section (`interrupt::free`) to make the access to the
+        // `static mut` variable interrupt safe which is required for memory safety
+        interrupt::free(|_| unsafe {
+            static mut HSTDOUT: Option<HStdout> = None;
+
+            let hstdout = &mut *ptr::addr_of_mut!(HSTDOUT);
+
+            // lazy initialization
+            if hstdout.is_none() {
+                *hstdout = Some(hio::hstdout()?);
+            }
+
+            hstdout.as_mut().unwrap().write_all(&[address])
+        })
+        .ok(); // `.ok()` = ignore errors
+    }
+}
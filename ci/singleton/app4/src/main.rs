@@ -0,0 +1,58 @@
+#![no_main]
+#![no_std]
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use cortex_m_semihosting::{debug, hio};
+
+use log::{global_logger, log, GlobalLog, Level};
+use rt::entry;
+
+// A logger whose (imaginary) output buffer is full every third message
+struct Logger {
+    count: AtomicU32,
+}
+
+static HEALTH_LOGGER: Logger = Logger {
+    count: AtomicU32::new(0),
+};
+
+global_logger!(HEALTH_LOGGER);
+
+entry!(main);
+
+fn main() -> ! {
+    for _ in 0..2 {
+        log!("Hello, world!");
+
+        log!("How are you?");
+
+        log!("Goodbye");
+    }
+
+    // print the number of messages sent, dropped and corrupted
+    let stats = log::stats();
+    if let Ok(mut hstdout) = hio::hstdout() {
+        hstdout
+            .write_all(&[
+                stats.sent as u8,
+                stats.dropped as u8,
+                stats.crc_errors as u8,
+            ])
+            .ok();
+    }
+
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
+
+impl GlobalLog for Logger {
+    fn log(&self, _level: Level, _address: u8) {
+        if self.count.fetch_add(1, Ordering::Relaxed) % 3 == 2 {
+            log::record_dropped();
+        } else {
+            log::record_sent();
+        }
+    }
+}
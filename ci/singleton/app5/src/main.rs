@@ -0,0 +1,57 @@
+#![no_main]
+#![no_std]
+
+use core::{arch::asm, ptr};
+
+use cortex_m_semihosting::{debug, hio};
+
+use log::{global_logger, log, RingLogger};
+use rt::{entry, exception};
+
+// Interrupt Control and State Register
+const ICSR: *mut u32 = 0xE000_ED04 as *mut u32;
+const PENDSVSET: u32 = 1 << 28;
+
+static RING_LOGGER: RingLogger<8> = RingLogger::new();
+
+global_logger!(RING_LOGGER);
+
+entry!(main);
+
+fn main() -> ! {
+    for _ in 0..2 {
+        log!("Hello from main");
+
+        // `PendSV` preempts `main` and logs from a higher priority
+        unsafe {
+            ptr::write_volatile(ICSR, PENDSVSET);
+            asm!("isb");
+        }
+
+        log!("Back in main");
+    }
+
+    // flush the buffered bytes and then print the number of messages sent and dropped
+    if let Ok(mut hstdout) = hio::hstdout() {
+        unsafe {
+            RING_LOGGER.drain(|byte| {
+                hstdout.write_all(&[byte]).ok();
+            })
+        }
+
+        let stats = log::stats();
+        hstdout
+            .write_all(&[stats.sent as u8, stats.dropped as u8])
+            .ok();
+    }
+
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
+
+exception!(PendSV, pend_sv);
+
+fn pend_sv() {
+    log!("Hello from PendSV");
+}
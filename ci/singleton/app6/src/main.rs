@@ -0,0 +1,55 @@
+#![no_main]
+#![no_std]
+
+use cortex_m::interrupt;
+use cortex_m_semihosting::{
+    debug,
+    hio::{self, HStdout},
+};
+
+use log::{global_logger, log, GlobalLog, Level};
+use rt::entry;
+
+struct Logger;
+
+global_logger!(Logger);
+
+entry!(main);
+
+fn main() -> ! {
+    log!("Hello, world!");
+
+    let voltage: u16 = 0x0c80;
+    log!("Battery voltage = {} mV", voltage);
+
+    log!("Goodbye");
+
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
+
+impl GlobalLog for Logger {
+    fn log(&self, level: Level, address: u8) {
+        self.log_args(level, address, &[])
+    }
+
+    fn log_args(&self, _level: Level, address: u8, args: &[u8]) {
+        // we use a critical section (`interrupt::free`) to make the access to the
+        // `static mut` variable interrupt safe which is required for memory safety
+        interrupt::free(|_| unsafe {
+            static mut HSTDOUT: Option<HStdout> = None;
+
+            // lazy initialization
+            if HSTDOUT.is_none() {
+                HSTDOUT = Some(hio::hstdout()?);
+            }
+
+            let hstdout = HSTDOUT.as_mut().unwrap();
+
+            // the address of the format string is followed by the arguments
+            hstdout.write_all(&[address])?;
+            hstdout.write_all(args)
+        }).ok(); // `.ok()` = ignore errors
+    }
+}
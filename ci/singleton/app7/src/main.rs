@@ -0,0 +1,58 @@
+#![no_main]
+#![no_std]
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use cortex_m_semihosting::{debug, hio};
+
+use log::{global_logger, log, GlobalLog, Level};
+use rt::entry;
+
+// A logger with a fake clock that advances each time it's read
+struct Logger {
+    clock: AtomicU32,
+    // timestamp of the last message
+    last: AtomicU32,
+}
+
+static CLOCK_LOGGER: Logger = Logger {
+    clock: AtomicU32::new(0),
+    last: AtomicU32::new(0),
+};
+
+global_logger!(CLOCK_LOGGER);
+
+entry!(main);
+
+fn main() -> ! {
+    log!("Hello, world!");
+
+    log!("Goodbye");
+
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
+
+impl GlobalLog for Logger {
+    fn log(&self, level: Level, address: u8) {
+        self.log_timestamped(level, address, self.timestamp(), &[])
+    }
+
+    fn timestamp(&self) -> u32 {
+        self.clock.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    fn log_timestamped(&self, _level: Level, address: u8, timestamp: u32, _args: &[u8]) {
+        // the timestamps must be strictly increasing
+        if timestamp <= self.last.swap(timestamp, Ordering::Relaxed) {
+            debug::exit(debug::EXIT_FAILURE);
+        }
+
+        if let Ok(mut hstdout) = hio::hstdout() {
+            // the address of the string is followed by the timestamp
+            hstdout.write_all(&[address]).ok();
+            hstdout.write_all(&timestamp.to_le_bytes()).ok();
+        }
+    }
+}
@@ -0,0 +1,58 @@
+#![no_main]
+#![no_std]
+
+use cortex_m_semihosting::{debug, hio};
+
+use log::{global_logger, log, Drain, GlobalLogger, Logger, SemihostingDrain};
+use rt::entry;
+
+static SEMIHOSTING_LOGGER: GlobalLogger<SemihostingDrain> =
+    GlobalLogger::new(SemihostingDrain::new());
+
+global_logger!(SEMIHOSTING_LOGGER);
+
+entry!(main);
+
+fn main() -> ! {
+    // the global logger sends the messages to the host right away
+    log!("Hello, world!");
+
+    log!("Goodbye");
+
+    // this logger writes the messages into memory
+    let mut logger = Logger::new(Buffer {
+        bytes: [0; 4],
+        len: 0,
+    });
+
+    let _ = log!(logger, "Hello from RAM");
+
+    let _ = log!(logger, "Goodbye from RAM");
+
+    // now send the buffered messages to the host
+    let buffer = logger.into_inner();
+    if let Ok(mut hstdout) = hio::hstdout() {
+        hstdout.write_all(&buffer.bytes[..buffer.len]).ok();
+    }
+
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
+
+// An in-memory drain
+struct Buffer {
+    bytes: [u8; 4],
+    len: usize,
+}
+
+impl Drain for Buffer {
+    type Error = ();
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), ()> {
+        let end = self.len + bytes.len();
+        self.bytes.get_mut(self.len..end).ok_or(())?.copy_from_slice(bytes);
+        self.len = end;
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m_semihosting::debug;
+
+use log::{GlobalLog, ItmLogger, Level};
+use rt::{entry, exception};
+
+// SysTick registers
+const SYST_CSR: *mut u32 = 0xE000_E010 as *mut u32;
+const SYST_RVR: *mut u32 = 0xE000_E014 as *mut u32;
+
+// QEMU doesn't emulate the ITM so we use a mock of the stimulus port registers
+static mut STIM: [u32; 32] = [0; 32];
+
+const PORT: u8 = 3;
+
+entry!(main);
+
+fn main() -> ! {
+    let logger = unsafe { ItmLogger::with_base_address(ptr::addr_of!(STIM) as usize, PORT) };
+
+    unsafe {
+        // `SysTick` marks the FIFO as ready after a while
+        ptr::write_volatile(SYST_RVR, 0x1_0000);
+        ptr::write_volatile(SYST_CSR, 0b111);
+    }
+
+    // NOTE the FIFO is not ready yet so this busy waits until `SysTick` runs
+    logger.log(Level::Info, 0x2a);
+
+    unsafe {
+        let stim = ptr::addr_of!(STIM) as *const u32;
+        for port in 0..32 {
+            let expected = if port == PORT as usize { 0x2a } else { 0 };
+
+            if ptr::read_volatile(stim.add(port)) != expected {
+                debug::exit(debug::EXIT_FAILURE);
+            }
+        }
+    }
+
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
+
+exception!(SysTick, sys_tick);
+
+fn sys_tick() {
+    unsafe {
+        let port = (ptr::addr_of_mut!(STIM) as *mut u32).add(PORT as usize);
+
+        // the logger must not write to the port before the FIFO is ready
+        if ptr::read_volatile(port) != 0 {
+            debug::exit(debug::EXIT_FAILURE);
+        }
+
+        // set the FIFO ready bit and stop the timer
+        ptr::write_volatile(port, 1);
+        ptr::write_volatile(SYST_CSR, 0);
+    }
+}
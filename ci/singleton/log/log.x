@@ -1 +0,0 @@
-../../logging/log/log.x
\ No newline at end of file
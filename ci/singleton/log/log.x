@@ -0,0 +1,12 @@
+SECTIONS
+{
+  .log 0 (INFO) : {
+    _slog = .;
+    *(.log);
+    _elog = .;
+  }
+}
+
+/* The addresses of the interned strings are sent as `u16` values */
+ASSERT(_elog - _slog <= 0x10000, "
+ERROR(log): too many interned strings; their addresses don't fit in a `u16`");
@@ -2,13 +2,13 @@
 
 // NEW!
 pub trait GlobalLog: Sync {
-    fn log(&self, address: u8);
+    fn log(&self, address: u16);
 }
 
 pub trait Log {
     type Error;
 
-    fn log(&mut self, address: u8) -> Result<(), Self::Error>;
+    fn log(&mut self, address: u16) -> Result<(), Self::Error>;
 }
 
 #[macro_export]
@@ -24,7 +24,7 @@ macro_rules! log {
             #[link_section = ".log"]
             static SYMBOL: u8 = 0;
 
-            $crate::GlobalLog::log(LOGGER, &SYMBOL as *const u8 as usize as u8)
+            $crate::GlobalLog::log(LOGGER, &SYMBOL as *const u8 as usize as u16)
         }
     };
 
@@ -33,7 +33,7 @@ macro_rules! log {
         #[link_section = ".log"]
         static SYMBOL: u8 = 0;
 
-        $crate::Log::log(&mut $logger, &SYMBOL as *const u8 as usize as u8)
+        $crate::Log::log(&mut $logger, &SYMBOL as *const u8 as usize as u16)
     }};
 }
 
@@ -0,0 +1 @@
+../../logging/log/build.rs
\ No newline at end of file
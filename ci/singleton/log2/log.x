@@ -0,0 +1 @@
+../../logging/log/log.x
\ No newline at end of file
@@ -0,0 +1,41 @@
+/// Maximum number of bytes that the arguments of a single `log!` call can take
+pub const MAX_ARGS_LEN: usize = 32;
+
+/// The arguments of a `log!` call serialized as little-endian bytes, in order
+///
+/// Used by the `log!` macro; not meant to be used directly
+#[doc(hidden)]
+pub struct Args {
+    buffer: [u8; MAX_ARGS_LEN],
+    len: usize,
+}
+
+impl Args {
+    pub const fn new() -> Self {
+        Args {
+            buffer: [0; MAX_ARGS_LEN],
+            len: 0,
+        }
+    }
+
+    /// # Panics
+    ///
+    /// This method panics if the arguments take more than `MAX_ARGS_LEN` bytes
+    pub fn push(&mut self, bytes: &[u8]) {
+        let end = self.len + bytes.len();
+        assert!(end <= MAX_ARGS_LEN);
+
+        self.buffer[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer[..self.len]
+    }
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Args::new()
+    }
+}
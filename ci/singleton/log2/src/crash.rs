@@ -0,0 +1,94 @@
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicU32, AtomicUsize, Ordering},
+};
+
+use crate::{GlobalLog, Level};
+
+// marks the contents of the ring buffer as valid
+const MAGIC: u32 = 0xC0FF_EE42;
+
+/// A global logger that keeps the last `N` logged bytes in a ring buffer
+///
+/// The logger is meant to be placed in the `.uninit` section, which `Reset` neither zeroes nor
+/// initializes, so that the bytes logged before a crash / reset can be read back using `dump` on the
+/// next boot:
+///
+/// ``` ignore
+/// #[link_section = ".uninit.CRASH_LOGGER"]
+/// static CRASH_LOGGER: CrashLogger<64> = CrashLogger::new();
+/// ```
+///
+/// NOTE the initial value given to the `static` is discarded by the linker (`.uninit` is a `NOLOAD`
+/// section); `log` and `dump` detect a buffer that holds garbage, like the one you get after a
+/// power-on reset, using a magic marker.
+pub struct CrashLogger<const N: usize> {
+    magic: AtomicU32,
+    // number of bytes logged since the buffer was (re)started
+    head: AtomicUsize,
+    buffer: UnsafeCell<[u8; N]>,
+}
+
+unsafe impl<const N: usize> Sync for CrashLogger<N> {}
+
+impl<const N: usize> CrashLogger<N> {
+    /// Creates a new crash logger
+    pub const fn new() -> Self {
+        CrashLogger {
+            magic: AtomicU32::new(0),
+            head: AtomicUsize::new(0),
+            buffer: UnsafeCell::new([0; N]),
+        }
+    }
+
+    /// Returns the last `N` (or fewer) logged bytes, oldest first
+    ///
+    /// Returns an empty slice if the buffer doesn't hold valid data
+    ///
+    /// # Safety
+    ///
+    /// This reorders the ring buffer in place so it must not be called while `log` may run, e.g. call
+    /// it early in `main`, before enabling interrupts. The returned slice is overwritten by the next
+    /// `log` call.
+    pub unsafe fn dump(&self) -> &[u8] {
+        if self.magic.load(Ordering::Relaxed) != MAGIC {
+            return &[];
+        }
+
+        let head = self.head.load(Ordering::Relaxed);
+        let buffer = &mut *self.buffer.get();
+
+        if head <= N {
+            &buffer[..head]
+        } else {
+            // the buffer has wrapped around; move the oldest byte to the front
+            buffer.rotate_left(head % N);
+            self.head.store(N, Ordering::Relaxed);
+
+            &buffer[..]
+        }
+    }
+}
+
+impl<const N: usize> Default for CrashLogger<N> {
+    fn default() -> Self {
+        CrashLogger::new()
+    }
+}
+
+impl<const N: usize> GlobalLog for CrashLogger<N> {
+    // NOTE only the address is recorded
+    fn log(&self, _level: Level, address: u8) {
+        if self.magic.load(Ordering::Relaxed) != MAGIC {
+            // garbage; start over
+            self.head.store(0, Ordering::Relaxed);
+            self.magic.store(MAGIC, Ordering::Relaxed);
+        }
+
+        let i = self.head.fetch_add(1, Ordering::Relaxed);
+
+        unsafe {
+            (*self.buffer.get())[i % N] = address;
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use core::{
+    cell::UnsafeCell,
+    cmp,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{GlobalLog, Level, Log};
+
+/// The transport that carries the log bytes to the host, e.g. semihosting, an ITM port or a UART
+pub trait Drain {
+    type Error;
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+
+    /// Sends out any buffered bytes; the default implementation does nothing
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// A `Log` implementation that writes the messages to a `Drain`
+pub struct Logger<D> {
+    drain: D,
+}
+
+impl<D> Logger<D> {
+    /// Creates a logger that writes to `drain`
+    pub const fn new(drain: D) -> Self {
+        Logger { drain }
+    }
+
+    /// Returns the drain this logger writes to
+    pub fn into_inner(self) -> D {
+        self.drain
+    }
+}
+
+impl<D> Log for Logger<D>
+where
+    D: Drain,
+{
+    type Error = D::Error;
+
+    fn log(&mut self, address: u8) -> Result<(), D::Error> {
+        self.drain.write_bytes(&[address])
+    }
+}
+
+/// A `GlobalLog` implementation that writes the messages to a `Drain`
+///
+/// Each message is sent as its level (see `Level`), followed by its address, its timestamp (4 bytes,
+/// little endian) and then its arguments
+///
+/// ``` ignore
+/// static SEMIHOSTING_LOGGER: GlobalLogger<SemihostingDrain> =
+///     GlobalLogger::new(SemihostingDrain::new());
+///
+/// global_logger!(SEMIHOSTING_LOGGER);
+/// ```
+///
+/// The drain is not shared between contexts: a message logged while another context is writing to
+/// the drain (i.e. from an interrupt handler that preempted `log`) is dropped and counted (see
+/// `stats`) rather than interleaved with the message being written.
+pub struct GlobalLogger<D> {
+    busy: AtomicBool,
+    drain: UnsafeCell<D>,
+}
+
+impl<D> GlobalLogger<D> {
+    /// Creates a global logger that writes to `drain`
+    pub const fn new(drain: D) -> Self {
+        GlobalLogger {
+            busy: AtomicBool::new(false),
+            drain: UnsafeCell::new(drain),
+        }
+    }
+}
+
+// NOTE the `busy` flag grants exclusive access to the drain
+unsafe impl<D> Sync for GlobalLogger<D> where D: Send {}
+
+impl<D> GlobalLog for GlobalLogger<D>
+where
+    D: Drain + Send,
+{
+    fn log(&self, level: Level, address: u8) {
+        self.log_args(level, address, &[])
+    }
+
+    fn log_args(&self, level: Level, address: u8, args: &[u8]) {
+        self.log_timestamped(level, address, self.timestamp(), args)
+    }
+
+    fn log_timestamped(&self, level: Level, address: u8, timestamp: u32, args: &[u8]) {
+        if self.busy.swap(true, Ordering::Acquire) {
+            crate::record_dropped();
+            return;
+        }
+
+        let drain = unsafe { &mut *self.drain.get() };
+        let res = drain
+            .write_bytes(&[level as u8, address])
+            .and_then(|_| drain.write_bytes(&timestamp.to_le_bytes()))
+            .and_then(|_| drain.write_bytes(args));
+
+        self.busy.store(false, Ordering::Release);
+
+        match res {
+            Ok(()) => crate::record_sent(),
+            Err(_) => crate::record_dropped(),
+        }
+    }
+
+    fn flush(&self) {
+        // NOTE if another context is writing to the drain the bytes will be flushed later
+        if self.busy.swap(true, Ordering::Acquire) {
+            return;
+        }
+
+        unsafe { (*self.drain.get()).flush().ok() };
+
+        self.busy.store(false, Ordering::Release);
+    }
+}
+
+/// A `Drain` that accumulates the bytes in a buffer of `N` bytes and writes them to the inner drain
+/// only when the buffer is full or when it is explicitly flushed
+///
+/// This greatly reduces the number of writes, each of which is very expensive with drains like
+/// semihosting that halt the processor. Remember to `flush` the logger before exiting!
+pub struct BufferedDrain<D, const N: usize> {
+    drain: D,
+    buffer: [u8; N],
+    len: usize,
+}
+
+impl<D, const N: usize> BufferedDrain<D, N> {
+    /// Creates a drain that buffers the bytes before writing them to `drain`
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `N` is zero
+    pub const fn new(drain: D) -> Self {
+        assert!(N != 0);
+
+        BufferedDrain {
+            drain,
+            buffer: [0; N],
+            len: 0,
+        }
+    }
+}
+
+impl<D, const N: usize> Drain for BufferedDrain<D, N>
+where
+    D: Drain,
+{
+    type Error = D::Error;
+
+    fn write_bytes(&mut self, mut bytes: &[u8]) -> Result<(), D::Error> {
+        while !bytes.is_empty() {
+            let n = cmp::min(N - self.len, bytes.len());
+            self.buffer[self.len..self.len + n].copy_from_slice(&bytes[..n]);
+            self.len += n;
+            bytes = &bytes[n..];
+
+            if self.len == N {
+                self.flush()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), D::Error> {
+        if self.len != 0 {
+            let len = self.len;
+            self.len = 0;
+            self.drain.write_bytes(&self.buffer[..len])?;
+        }
+
+        self.drain.flush()
+    }
+}
+
+/// A `Drain` that sends the bytes to the host's stdout using semihosting
+#[cfg(feature = "semihosting")]
+pub struct SemihostingDrain {
+    hstdout: Option<cortex_m_semihosting::hio::HStdout>,
+}
+
+#[cfg(feature = "semihosting")]
+impl SemihostingDrain {
+    /// Creates a new drain; the semihosting stdout is opened on the first write
+    pub const fn new() -> Self {
+        SemihostingDrain { hstdout: None }
+    }
+}
+
+#[cfg(feature = "semihosting")]
+impl Default for SemihostingDrain {
+    fn default() -> Self {
+        SemihostingDrain::new()
+    }
+}
+
+#[cfg(feature = "semihosting")]
+impl Drain for SemihostingDrain {
+    type Error = ();
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), ()> {
+        // lazy initialization
+        if self.hstdout.is_none() {
+            self.hstdout = Some(cortex_m_semihosting::hio::hstdout()?);
+        }
+
+        self.hstdout.as_mut().unwrap().write_all(bytes)
+    }
+}
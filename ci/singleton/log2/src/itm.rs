@@ -0,0 +1,64 @@
+use core::ptr;
+
+use crate::{GlobalLog, Level};
+
+// Address of the first ITM stimulus port register
+const ITM_STIM: usize = 0xE000_0000;
+
+/// A global logger that writes the logged bytes to an ITM stimulus port
+///
+/// Unlike semihosting, ITM doesn't halt the processor so logging is fast. The debugger is in charge
+/// of enabling the ITM and the selected stimulus port; otherwise the bytes are silently discarded.
+///
+/// ``` ignore
+/// static ITM_LOGGER: ItmLogger = ItmLogger::new(0);
+///
+/// global_logger!(ITM_LOGGER);
+/// ```
+///
+/// NOTE each message is a single byte written with a single store so messages logged from different
+/// priorities never get interleaved; however, a context that preempts `log` between the FIFO check
+/// and the write may fill the FIFO, in which case the preempted byte is lost.
+pub struct ItmLogger {
+    stim: usize,
+}
+
+impl ItmLogger {
+    /// Creates a logger that writes to the stimulus `port`
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `port` is greater than 31
+    pub const fn new(port: u8) -> Self {
+        unsafe { Self::with_base_address(ITM_STIM, port) }
+    }
+
+    /// Like `new` but the stimulus port registers start at address `base`; used for testing
+    ///
+    /// # Safety
+    ///
+    /// `base` must point to a (mock) block of stimulus port registers
+    #[doc(hidden)]
+    pub const unsafe fn with_base_address(base: usize, port: u8) -> Self {
+        assert!(port < 32);
+
+        ItmLogger {
+            stim: base + 4 * port as usize,
+        }
+    }
+}
+
+impl GlobalLog for ItmLogger {
+    // NOTE only the address is sent
+    fn log(&self, _level: Level, address: u8) {
+        unsafe {
+            // wait until the FIFO is ready to accept more data
+            while ptr::read_volatile(self.stim as *const u32) & 1 == 0 {}
+
+            // NOTE a byte sized write sends a single byte
+            ptr::write_volatile(self.stim as *mut u8, address);
+        }
+
+        crate::record_sent();
+    }
+}
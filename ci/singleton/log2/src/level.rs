@@ -0,0 +1,12 @@
+/// The severity of a log message
+///
+/// The most severe level comes first; `Level::Error < Level::Trace`
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[repr(u8)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
@@ -0,0 +1,238 @@
+#![no_std]
+
+mod args;
+mod crash;
+mod drain;
+mod itm;
+mod level;
+mod ring;
+mod stats;
+mod tee;
+
+pub use args::{Args, MAX_ARGS_LEN};
+pub use crash::CrashLogger;
+#[cfg(feature = "semihosting")]
+pub use drain::SemihostingDrain;
+pub use drain::{BufferedDrain, Drain, GlobalLogger, Logger};
+pub use itm::ItmLogger;
+pub use level::Level;
+pub use ring::RingLogger;
+pub use stats::{record_crc_error, record_dropped, record_sent, stats, LogStats};
+pub use tee::Tee;
+
+pub trait GlobalLog: Sync {
+    /// Logs the message at `address` with severity `level`
+    fn log(&self, level: Level, address: u8);
+
+    /// Logs a message that has runtime arguments
+    ///
+    /// `args` are the arguments of the logging macro call serialized as little-endian bytes; they are meant
+    /// to be sent right after the `address` of the format string. The default implementation drops
+    /// the arguments and only logs the `address`.
+    fn log_args(&self, level: Level, address: u8, args: &[u8]) {
+        let _ = args;
+        self.log(level, address)
+    }
+
+    /// Returns the current time, e.g. the value of the DWT cycle counter
+    ///
+    /// `log!` reads this before logging a message. The default implementation returns 0.
+    fn timestamp(&self) -> u32 {
+        0
+    }
+
+    /// Logs a message tagged with the `timestamp` returned by `timestamp`
+    ///
+    /// This is what the logging macros call. The default implementation drops the timestamp and calls
+    /// `log_args`.
+    fn log_timestamped(&self, level: Level, address: u8, timestamp: u32, args: &[u8]) {
+        let _ = timestamp;
+        self.log_args(level, address, args)
+    }
+
+    /// Sends out the messages that the logger may have buffered
+    ///
+    /// Call this before exiting the program. The default implementation does nothing.
+    fn flush(&self) {}
+}
+
+pub trait Log {
+    type Error;
+
+    fn log(&mut self, address: u8) -> Result<(), Self::Error>;
+}
+
+/// Logs messages at the INFO log level
+///
+/// `log!(logger, "message")` logs to a local `Log`ger instead of the global one
+#[macro_export]
+macro_rules! log {
+    ($string:expr) => {
+        $crate::__log!($crate::Level::Info, $string)
+    };
+
+    ($string:literal, $($arg:expr),+ $(,)?) => {
+        $crate::__log!($crate::Level::Info, $string, $($arg),+)
+    };
+
+    ($logger:expr, $string:expr) => {
+        $crate::Log::log(&mut $logger, $crate::__intern!($string))
+    };
+}
+
+/// Logs messages at the ERROR log level
+#[macro_export]
+macro_rules! error {
+    ($($tt:tt)+) => {
+        $crate::__log!($crate::Level::Error, $($tt)+)
+    };
+}
+
+/// Logs messages at the WARNING log level
+#[macro_export]
+macro_rules! warn {
+    ($($tt:tt)+) => {
+        $crate::__log!($crate::Level::Warn, $($tt)+)
+    };
+}
+
+/// Logs messages at the INFO log level
+#[macro_export]
+macro_rules! info {
+    ($($tt:tt)+) => {
+        $crate::__log!($crate::Level::Info, $($tt)+)
+    };
+}
+
+/// Logs messages at the DEBUG log level
+#[macro_export]
+macro_rules! debug {
+    ($($tt:tt)+) => {
+        $crate::__log!($crate::Level::Debug, $($tt)+)
+    };
+}
+
+/// Logs messages at the TRACE log level
+#[macro_export]
+macro_rules! trace {
+    ($($tt:tt)+) => {
+        $crate::__log!($crate::Level::Trace, $($tt)+)
+    };
+}
+
+/// Sends a message with severity `$level` to the global logger
+///
+/// All the levels share the `.log` section; the level is passed to the logger at runtime
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log {
+    ($level:expr, $string:expr) => {{
+        let address = $crate::__intern!($string);
+
+        unsafe {
+            extern "Rust" {
+                static LOGGER: &'static dyn $crate::GlobalLog;
+            }
+
+            let timestamp = $crate::GlobalLog::timestamp(LOGGER);
+            $crate::GlobalLog::log_timestamped(LOGGER, $level, address, timestamp, &[])
+        }
+    }};
+
+    // the format string is interned; the arguments must be integers (they are sent using
+    // `to_le_bytes`)
+    ($level:expr, $string:literal, $($arg:expr),+ $(,)?) => {{
+        let address = $crate::__intern!($string);
+
+        unsafe {
+            extern "Rust" {
+                static LOGGER: &'static dyn $crate::GlobalLog;
+            }
+
+            let mut args = $crate::Args::new();
+            $(args.push(&($arg).to_le_bytes());)+
+
+            let timestamp = $crate::GlobalLog::timestamp(LOGGER);
+            $crate::GlobalLog::log_timestamped(LOGGER, $level, address, timestamp, args.as_bytes())
+        }
+    }};
+}
+
+/// Flushes the global logger; see `GlobalLog::flush`
+#[macro_export]
+macro_rules! flush {
+    () => {
+        unsafe {
+            extern "Rust" {
+                static LOGGER: &'static dyn $crate::GlobalLog;
+            }
+
+            $crate::GlobalLog::flush(LOGGER)
+        }
+    };
+}
+
+/// Interns `$string` into the `.log` section and returns its address
+///
+/// All the uses of the same string share a single symbol (and thus address): within an object file
+/// the symbol is only defined once (`.ifndef`) and across object files the linker keeps a single copy
+/// of the symbol (`comdat` group).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __intern {
+    ($string:expr) => {{
+        // NOTE(mod) `global_asm!` can't be used in statement position
+        mod string {
+            // NOTE(raw) the string may contain braces, e.g. `{}`, which are not placeholders
+            ::core::arch::global_asm!(
+                concat!(
+                    ".ifndef \"", $string, "\"\n",
+                    ".pushsection .log,\"aG\",%progbits,\"", $string, "\",comdat\n",
+                    ".globl \"", $string, "\"\n",
+                    ".type \"", $string, "\",%object\n",
+                    ".size \"", $string, "\",1\n",
+                    "\"", $string, "\":\n",
+                    ".byte 0\n",
+                    ".popsection\n",
+                    ".endif\n",
+                ),
+                options(raw)
+            );
+        }
+
+        extern "Rust" {
+            #[link_name = $string]
+            static SYMBOL: u8;
+        }
+
+        unsafe { &SYMBOL as *const u8 as usize as u8 }
+    }};
+}
+
+#[macro_export]
+macro_rules! global_logger {
+    ($logger:expr) => {
+        #[no_mangle]
+        pub static LOGGER: &dyn $crate::GlobalLog = $crate::__global_logger(&$logger);
+
+        // a second `global_logger!` invocation anywhere in the crate is rejected with "the name
+        // `__global_logger_defined_multiple_times` is defined multiple times"
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! __global_logger_defined_multiple_times {
+            () => {};
+        }
+    };
+}
+
+/// Used by `global_logger!` to check the type of the logger
+#[doc(hidden)]
+pub const fn __global_logger<L>(logger: &'static L) -> &'static dyn GlobalLog
+where
+    // the argument of `global_logger!` must implement `GlobalLog`. `GlobalLog` already requires
+    // `Sync` but the explicit bound makes a non-`Sync` logger fail here with "cannot be shared
+    // between threads safely"
+    L: Sync + GlobalLog,
+{
+    logger
+}
@@ -0,0 +1,114 @@
+use core::sync::atomic::{AtomicU16, AtomicUsize, Ordering};
+
+use crate::{GlobalLog, Level};
+
+// marks a slot that holds a logged byte
+const FULL: u16 = 1 << 8;
+
+/// A global logger that stores the logged bytes in a ring buffer and never disables interrupts
+///
+/// `log` only pushes the byte into the buffer; a low priority task (e.g. the idle loop in `main`)
+/// is in charge of sending the buffered bytes to the host using `drain`:
+///
+/// ``` ignore
+/// static RING_LOGGER: RingLogger<64> = RingLogger::new();
+///
+/// global_logger!(RING_LOGGER);
+///
+/// loop {
+///     unsafe {
+///         RING_LOGGER.drain(|byte| {
+///             hstdout.write_all(&[byte]).ok();
+///         })
+///     }
+/// }
+/// ```
+///
+/// `log` can be called from any priority. NOTE a spin lock can't be used to serialize the producers
+/// on a single core system: an interrupt handler would spin forever waiting for the lower priority
+/// context it preempted to release the lock. Instead, each call to `log` reserves a slot using a
+/// compare-and-swap loop and then fills it in; `drain` stops at the first reserved slot that has not
+/// been filled yet. When the buffer is full the byte is dropped and counted (see `stats`).
+pub struct RingLogger<const N: usize> {
+    // index of the next slot to reserve; only grows
+    head: AtomicUsize,
+    // index of the next slot to drain; only grows
+    tail: AtomicUsize,
+    slots: [AtomicU16; N],
+}
+
+impl<const N: usize> RingLogger<N> {
+    // NOTE `AtomicU16` is not `Copy` so the array repeat expression in `new` needs a constant; each
+    // use of `EMPTY` is a new, independent, atomic
+    #[allow(clippy::declare_interior_mutable_const)]
+    const EMPTY: AtomicU16 = AtomicU16::new(0);
+
+    /// Creates a new, empty, ring logger
+    pub const fn new() -> Self {
+        RingLogger {
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            slots: [Self::EMPTY; N],
+        }
+    }
+
+    /// Passes the buffered bytes, oldest first, to `f` and removes them from the buffer
+    ///
+    /// # Safety
+    ///
+    /// There must be a single consumer: this must not be called from more than one context (e.g. both
+    /// `main` and an interrupt handler).
+    pub unsafe fn drain(&self, mut f: impl FnMut(u8)) {
+        let mut tail = self.tail.load(Ordering::Relaxed);
+
+        loop {
+            let slot = &self.slots[tail % N];
+            let value = slot.load(Ordering::Acquire);
+
+            if value & FULL == 0 {
+                // empty, or reserved by a `log` call that has been preempted
+                break;
+            }
+
+            f(value as u8);
+
+            slot.store(0, Ordering::Relaxed);
+            tail = tail.wrapping_add(1);
+            self.tail.store(tail, Ordering::Release);
+        }
+    }
+}
+
+impl<const N: usize> Default for RingLogger<N> {
+    fn default() -> Self {
+        RingLogger::new()
+    }
+}
+
+impl<const N: usize> GlobalLog for RingLogger<N> {
+    // NOTE only the address is recorded
+    fn log(&self, _level: Level, address: u8) {
+        // reserve a slot
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            if head.wrapping_sub(self.tail.load(Ordering::Acquire)) >= N {
+                crate::record_dropped();
+                return;
+            }
+
+            match self.head.compare_exchange_weak(
+                head,
+                head.wrapping_add(1),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(current) => head = current,
+            }
+        }
+
+        // fill it in
+        self.slots[head % N].store(FULL | u16::from(address), Ordering::Release);
+        crate::record_sent();
+    }
+}
@@ -0,0 +1,51 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// A snapshot of the logger health counters; see `stats`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LogStats {
+    /// Number of messages that were sent out
+    pub sent: u32,
+    /// Number of messages that were dropped, e.g. because the output buffer was full
+    pub dropped: u32,
+    /// Number of frames that failed their CRC check
+    pub crc_errors: u32,
+}
+
+// All the counters use `Relaxed` ordering: each one is updated with a single atomic read-modify-write
+// so no increment is ever lost, and no other memory is synchronized through them. The flip side is
+// that a snapshot taken while another context is logging may be off by one message between fields.
+static SENT: AtomicU32 = AtomicU32::new(0);
+static DROPPED: AtomicU32 = AtomicU32::new(0);
+static CRC_ERRORS: AtomicU32 = AtomicU32::new(0);
+
+/// Records that a message was sent out
+///
+/// To be called by `GlobalLog` implementations
+pub fn record_sent() {
+    SENT.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Records that a message was dropped
+///
+/// To be called by `GlobalLog` implementations
+pub fn record_dropped() {
+    DROPPED.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Records that a frame failed its CRC check
+///
+/// To be called by `GlobalLog` implementations that use a checksummed transport
+pub fn record_crc_error() {
+    CRC_ERRORS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Returns a snapshot of the logger health counters
+///
+/// This doesn't need a critical section; see the note about ordering in the source code
+pub fn stats() -> LogStats {
+    LogStats {
+        sent: SENT.load(Ordering::Relaxed),
+        dropped: DROPPED.load(Ordering::Relaxed),
+        crc_errors: CRC_ERRORS.load(Ordering::Relaxed),
+    }
+}
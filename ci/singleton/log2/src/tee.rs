@@ -0,0 +1,67 @@
+use crate::{GlobalLog, Level};
+
+/// A global logger that forwards every message to two loggers: a `ring` buffer, e.g. a
+/// `CrashLogger`, and a `live` sink, e.g. semihosting or ITM
+///
+/// ``` ignore
+/// #[link_section = ".uninit.CRASH_LOGGER"]
+/// static CRASH_LOGGER: CrashLogger<64> = CrashLogger::new();
+///
+/// static LIVE_LOGGER: Semihosting = Semihosting;
+///
+/// global_logger!(Tee::new(&CRASH_LOGGER, &LIVE_LOGGER));
+/// ```
+///
+/// Each branch keeps its own buffering and overflow policy: a full ring buffer drops its oldest
+/// messages and a stalled live sink drops (or blocks on) new messages but neither affects the other.
+///
+/// The `ring` is always written first so a crash while logging never leaves the live sink with a
+/// message that the ring buffer lacks. Within a single execution context both branches see the
+/// messages in the same order; if an interrupt handler logs while the thread is in the middle of
+/// `log` the two branches may disagree on the order of those two messages.
+pub struct Tee<R, L>
+where
+    R: 'static,
+    L: 'static,
+{
+    ring: &'static R,
+    live: &'static L,
+}
+
+impl<R, L> Tee<R, L> {
+    /// Creates a logger that forwards messages to `ring` and then to `live`
+    pub const fn new(ring: &'static R, live: &'static L) -> Self {
+        Tee { ring, live }
+    }
+}
+
+impl<R, L> GlobalLog for Tee<R, L>
+where
+    R: GlobalLog,
+    L: GlobalLog,
+{
+    fn log(&self, level: Level, address: u8) {
+        self.ring.log(level, address);
+        self.live.log(level, address);
+    }
+
+    fn log_args(&self, level: Level, address: u8, args: &[u8]) {
+        self.ring.log_args(level, address, args);
+        self.live.log_args(level, address, args);
+    }
+
+    // NOTE both branches get the timestamp of the `ring` logger
+    fn timestamp(&self) -> u32 {
+        self.ring.timestamp()
+    }
+
+    fn log_timestamped(&self, level: Level, address: u8, timestamp: u32, args: &[u8]) {
+        self.ring.log_timestamped(level, address, timestamp, args);
+        self.live.log_timestamped(level, address, timestamp, args);
+    }
+
+    fn flush(&self) {
+        self.ring.flush();
+        self.live.flush();
+    }
+}
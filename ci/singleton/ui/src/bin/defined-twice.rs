@@ -0,0 +1,28 @@
+//! This must NOT compile
+
+#![no_std]
+#![no_main]
+
+use core::panic::PanicInfo;
+
+use log::{GlobalLog, Level};
+
+struct Logger;
+
+impl GlobalLog for Logger {
+    fn log(&self, _level: Level, _address: u8) {}
+}
+
+mod a {
+    log::global_logger!(super::Logger);
+}
+
+mod b {
+    // error: the name `__global_logger_defined_multiple_times` is defined multiple times
+    log::global_logger!(super::Logger);
+}
+
+#[panic_handler]
+fn panic(_: &PanicInfo<'_>) -> ! {
+    loop {}
+}
@@ -0,0 +1,19 @@
+//! This must NOT compile
+
+#![no_std]
+#![no_main]
+
+use core::panic::PanicInfo;
+
+use log::global_logger;
+
+// doesn't implement `GlobalLog`
+struct Logger;
+
+// error: the trait bound `Logger: GlobalLog` is not satisfied
+global_logger!(Logger);
+
+#[panic_handler]
+fn panic(_: &PanicInfo<'_>) -> ! {
+    loop {}
+}
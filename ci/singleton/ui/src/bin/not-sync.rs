@@ -0,0 +1,23 @@
+//! This must NOT compile
+
+#![no_std]
+#![no_main]
+
+use core::{cell::Cell, panic::PanicInfo};
+
+use log::global_logger;
+
+// `Cell` is not `Sync` so this logger can't implement `GlobalLog`
+struct Logger {
+    count: Cell<u32>,
+}
+
+// error: `Cell<u32>` cannot be shared between threads safely
+global_logger!(Logger {
+    count: Cell::new(0)
+});
+
+#[panic_handler]
+fn panic(_: &PanicInfo<'_>) -> ! {
+    loop {}
+}